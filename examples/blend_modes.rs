@@ -0,0 +1,87 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    BlendMode, ColorStop, Gradient, Renderer, TextStyle,
+    font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.render_rectangle(
+        DVec2::ZERO,
+        128.0,
+        128.0,
+        DVec2::ZERO,
+        0.0,
+        Gradient::linear(
+            dvec2(0.0, 0.0),
+            dvec2(128.0, 0.0),
+            vec![
+                ColorStop::new(0.0, Srgba::new(0.1, 0.1, 0.4, 1.0)),
+                ColorStop::new(1.0, Srgba::new(0.9, 0.8, 0.3, 1.0)),
+            ],
+        )
+        .into(),
+    );
+
+    let blend_modes = [
+        BlendMode::Normal,
+        BlendMode::Additive,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+        BlendMode::Overlay,
+        BlendMode::Darken,
+        BlendMode::Lighten,
+        BlendMode::Erase,
+    ];
+
+    for (i, blend_mode) in blend_modes.into_iter().enumerate() {
+        let center = dvec2(16.0 + 32.0 * (i % 4) as f64, 32.0 + 64.0 * (i / 4) as f64);
+
+        image_renderer.set_blend_mode(blend_mode);
+
+        image_renderer.render_circle(
+            center - dvec2(0.0, 6.0),
+            10.0,
+            Srgba::new(1.0, 0.2, 0.2, 0.8).into(),
+        );
+        image_renderer.render_circle(
+            center + dvec2(6.0, 6.0),
+            10.0,
+            Srgba::new(0.2, 1.0, 0.2, 0.8).into(),
+        );
+        image_renderer.render_circle(
+            center + dvec2(-6.0, 6.0),
+            10.0,
+            Srgba::new(0.2, 0.2, 1.0, 0.8).into(),
+        );
+
+        image_renderer.set_blend_mode(BlendMode::Normal);
+
+        image_renderer.render_text(
+            &format!("{blend_mode:?}"),
+            center + dvec2(0.0, 20.0),
+            anchor2d::CGT,
+            8.0,
+            &TextStyle::default(),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+    }
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("blend_modes.png")
+        .unwrap();
+}
@@ -0,0 +1,114 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_6};
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    FillRule, Path, Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.render_rectangle_lines(
+        dvec2(8.0, 8.0),
+        56.0,
+        48.0,
+        DVec2::ZERO,
+        0.0,
+        1.0,
+        Srgba::new(0.5, 0.5, 0.5, 1.0),
+    );
+
+    image_renderer.push_clip_rect(dvec2(9.0, 9.0), 54.0, 46.0, DVec2::ZERO, 0.0);
+
+    for i in 0..6 {
+        image_renderer.render_text(
+            &format!("Row {i}"),
+            dvec2(12.0, 2.0 + 12.0 * i as f64),
+            anchor2d::LGT,
+            12.0,
+            &TextStyle::default(),
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+    }
+
+    image_renderer.render_circle(
+        dvec2(64.0, 56.0),
+        12.0,
+        Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
+    );
+
+    image_renderer.pop_clip();
+
+    let mut path = Path::new();
+    path.move_to(dvec2(72.0, 32.0))
+        .arc_to(DVec2::splat(24.0), 0.0, false, true, dvec2(120.0, 32.0))
+        .arc_to(DVec2::splat(24.0), 0.0, false, true, dvec2(72.0, 32.0))
+        .close();
+
+    image_renderer.push_clip_path(&path, FillRule::NonZero);
+
+    for i in 0..6 {
+        image_renderer.render_rectangle(
+            dvec2(64.0, 8.0 + 8.0 * i as f64),
+            64.0,
+            4.0,
+            DVec2::ZERO,
+            0.0,
+            Srgba::new(0.0, 1.0, 1.0, 1.0).into(),
+        );
+    }
+
+    image_renderer.push_clip_rect(dvec2(96.0, 32.0), 20.0, 20.0, dvec2(0.5, 0.5), FRAC_PI_6);
+
+    image_renderer.render_rectangle(
+        dvec2(64.0, 0.0),
+        64.0,
+        64.0,
+        DVec2::ZERO,
+        0.0,
+        Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+    );
+
+    image_renderer.pop_clip();
+    image_renderer.pop_clip();
+
+    let star = (0..10)
+        .map(|i| {
+            let radius = if i % 2 == 0 { 28.0 } else { 12.0 };
+
+            dvec2(64.0, 96.0) + DVec2::from_angle(-FRAC_PI_2 + FRAC_PI_2 * 0.4 * i as f64) * radius
+        })
+        .collect::<Vec<DVec2>>();
+
+    image_renderer.push_clip_polygon(&star);
+    image_renderer.push_rotation(FRAC_PI_6);
+
+    for i in 0..12 {
+        image_renderer.render_line(
+            dvec2(0.0, 40.0 + 8.0 * i as f64),
+            dvec2(160.0, 40.0 + 8.0 * i as f64),
+            3.0,
+            Srgba::new(1.0, 0.0, 1.0, 1.0),
+        );
+    }
+
+    image_renderer.pop_transform();
+    image_renderer.pop_clip();
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("clipping.png")
+        .unwrap();
+}
@@ -0,0 +1,109 @@
+use std::f64::consts::{FRAC_PI_2, PI};
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    CornerRadii, LineCap, LineJoin, Path, Renderer, StrokeStyle,
+    font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.set_stroke_style(StrokeStyle::dashed(vec![4.0, 2.0], 0.0));
+
+    for i in 0..4 {
+        let x = 8.0 + 16.0 * i as f64;
+
+        image_renderer.render_line(
+            dvec2(x, 8.0),
+            dvec2(x, 56.0),
+            1.0,
+            Srgba::new(0.5, 0.5, 0.5, 1.0),
+        );
+    }
+
+    image_renderer.render_rectangle_lines(
+        dvec2(64.0, 8.0),
+        56.0,
+        48.0,
+        DVec2::ZERO,
+        0.0,
+        2.0,
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.set_stroke_style(StrokeStyle::dashed(vec![8.0, 4.0, 2.0, 4.0], 4.0));
+
+    image_renderer.render_rounded_rectangle_lines(
+        dvec2(72.0, 16.0),
+        40.0,
+        32.0,
+        DVec2::ZERO,
+        0.0,
+        CornerRadii::uniform(8.0),
+        2.0,
+        Srgba::new(1.0, 0.0, 0.0, 1.0),
+    );
+
+    image_renderer.set_stroke_style(StrokeStyle::dotted(6.0));
+
+    image_renderer.render_circle_lines(
+        dvec2(32.0, 96.0),
+        24.0,
+        3.0,
+        Srgba::new(1.0, 1.0, 0.0, 1.0),
+    );
+
+    image_renderer.render_arc_lines(
+        dvec2(32.0, 96.0),
+        14.0,
+        PI,
+        16,
+        PI,
+        3.0,
+        Srgba::new(0.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.set_stroke_style(StrokeStyle::new(vec![6.0, 4.0], 0.0, LineCap::Square));
+
+    image_renderer.render_star_lines(
+        dvec2(96.0, 96.0),
+        24.0,
+        12.0,
+        -FRAC_PI_2,
+        5,
+        2.0,
+        Srgba::new(0.0, 1.0, 0.0, 1.0),
+    );
+
+    let mut path = Path::new();
+    path.move_to(dvec2(72.0, 124.0))
+        .quad_to(dvec2(96.0, 104.0), dvec2(120.0, 124.0));
+
+    image_renderer.set_stroke_style(StrokeStyle::new(vec![3.0], 0.0, LineCap::Round));
+
+    image_renderer.render_path_stroke(
+        &path,
+        2.0,
+        LineJoin::Round,
+        LineCap::Round,
+        Srgba::new(1.0, 0.0, 1.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("dashes.png")
+        .unwrap();
+}
@@ -0,0 +1,61 @@
+use std::f64::consts::PI;
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.render_ellipse(
+        dvec2(32.0, 32.0),
+        dvec2(24.0, 12.0),
+        PI / 6.0,
+        Srgba::new(1.0, 1.0, 1.0, 1.0).into(),
+    );
+
+    image_renderer.render_ellipse_lines(
+        dvec2(96.0, 32.0),
+        dvec2(24.0, 12.0),
+        -PI / 6.0,
+        2.0,
+        Srgba::new(1.0, 0.0, 0.0, 1.0),
+    );
+
+    image_renderer.render_elliptical_arc(
+        dvec2(32.0, 96.0),
+        dvec2(24.0, 16.0),
+        0.0,
+        PI / 4.0,
+        3.0 * PI / 2.0,
+        Srgba::new(0.0, 0.0, 1.0, 1.0).into(),
+    );
+
+    image_renderer.render_elliptical_arc_lines(
+        dvec2(96.0, 96.0),
+        dvec2(24.0, 16.0),
+        PI / 2.0,
+        0.0,
+        PI,
+        4.0,
+        Srgba::new(0.0, 1.0, 0.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("ellipses.png")
+        .unwrap();
+}
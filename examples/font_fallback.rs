@@ -0,0 +1,70 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_font_registry = ImageFontRegistry::default();
+
+    image_font_registry
+        .load_font(
+            String::from("Symbols"),
+            include_bytes!("dejavu_sans.ttf").to_vec(),
+        )
+        .unwrap();
+
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        image_font_registry,
+    );
+
+    let fallback_style = TextStyle {
+        fallback_font_names: vec![String::from("Symbols")],
+        ..TextStyle::default()
+    };
+
+    let text = "A ★ B ♥ C";
+    let metrics = image_renderer.measure_text(text, 16.0, &fallback_style);
+
+    image_renderer.render_rectangle_lines(
+        dvec2(64.0, 32.0),
+        metrics.width,
+        metrics.height,
+        dvec2(0.5, 0.5),
+        0.0,
+        1.0,
+        Srgba::new(0.3, 0.3, 0.6, 1.0),
+    );
+
+    image_renderer.render_text(
+        text,
+        dvec2(64.0, 32.0),
+        anchor2d::CGC,
+        16.0,
+        &fallback_style,
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.render_text(
+        "A ★ B ♥ C",
+        dvec2(64.0, 80.0),
+        anchor2d::CGC,
+        16.0,
+        &TextStyle::default(),
+        Srgba::new(1.0, 0.5, 0.5, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("font_fallback.png")
+        .unwrap();
+}
@@ -0,0 +1,61 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_font_registry = ImageFontRegistry::default();
+
+    image_font_registry
+        .load_font(
+            String::from("Heading"),
+            include_bytes!("roboto.ttf").to_vec(),
+        )
+        .unwrap();
+
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        image_font_registry,
+    );
+
+    image_renderer.render_text(
+        "Heading",
+        dvec2(64.0, 16.0),
+        anchor2d::CGT,
+        24.0,
+        &TextStyle::font(String::from("Heading")),
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.render_text(
+        "Default font",
+        dvec2(64.0, 64.0),
+        anchor2d::CGC,
+        16.0,
+        &TextStyle::default(),
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.render_text(
+        "Unregistered",
+        dvec2(64.0, 104.0),
+        anchor2d::CGC,
+        16.0,
+        &TextStyle::font(String::from("Missing")),
+        Srgba::new(1.0, 0.5, 0.5, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("fonts.png")
+        .unwrap();
+}
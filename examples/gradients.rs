@@ -0,0 +1,117 @@
+use std::f64::consts::FRAC_PI_2;
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    ColorSpace, ColorStop, CornerRadii, Gradient, GradientKind, Renderer, SpreadMode,
+    font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let red_to_blue = vec![
+        ColorStop::new(0.0, Srgba::new(1.0, 0.0, 0.0, 1.0)),
+        ColorStop::new(1.0, Srgba::new(0.0, 0.0, 1.0, 1.0)),
+    ];
+
+    image_renderer.render_rectangle(
+        dvec2(8.0, 8.0),
+        112.0,
+        12.0,
+        DVec2::ZERO,
+        0.0,
+        Gradient::linear(dvec2(8.0, 0.0), dvec2(120.0, 0.0), red_to_blue.clone()).into(),
+    );
+
+    image_renderer.render_rectangle(
+        dvec2(8.0, 24.0),
+        112.0,
+        12.0,
+        DVec2::ZERO,
+        0.0,
+        Gradient::new(
+            GradientKind::Linear {
+                start: dvec2(8.0, 0.0),
+                end: dvec2(120.0, 0.0),
+            },
+            red_to_blue.clone(),
+            SpreadMode::Pad,
+            ColorSpace::Oklab,
+        )
+        .into(),
+    );
+
+    image_renderer.render_rounded_rectangle(
+        dvec2(8.0, 40.0),
+        112.0,
+        12.0,
+        DVec2::ZERO,
+        0.0,
+        CornerRadii::uniform(6.0),
+        Gradient::new(
+            GradientKind::Linear {
+                start: dvec2(8.0, 0.0),
+                end: dvec2(36.0, 0.0),
+            },
+            red_to_blue.clone(),
+            SpreadMode::Reflect,
+            ColorSpace::LinearSrgb,
+        )
+        .into(),
+    );
+
+    image_renderer.render_circle(
+        dvec2(32.0, 88.0),
+        24.0,
+        Gradient::new(
+            GradientKind::Radial {
+                center: dvec2(24.0, 80.0),
+                radius: 8.0,
+            },
+            vec![
+                ColorStop::new(0.0, Srgba::new(1.0, 1.0, 1.0, 1.0)),
+                ColorStop::new(0.5, Srgba::new(1.0, 1.0, 0.0, 1.0)),
+                ColorStop::new(1.0, Srgba::new(0.0, 1.0, 0.0, 0.0)),
+            ],
+            SpreadMode::Repeat,
+            ColorSpace::Srgb,
+        )
+        .into(),
+    );
+
+    image_renderer.render_star(
+        dvec2(96.0, 88.0),
+        28.0,
+        14.0,
+        -FRAC_PI_2,
+        5,
+        Gradient::conic(
+            dvec2(96.0, 88.0),
+            0.0,
+            vec![
+                ColorStop::new(0.0, Srgba::new(1.0, 0.0, 0.0, 1.0)),
+                ColorStop::new(1.0 / 3.0, Srgba::new(0.0, 1.0, 0.0, 1.0)),
+                ColorStop::new(2.0 / 3.0, Srgba::new(0.0, 0.0, 1.0, 1.0)),
+                ColorStop::new(1.0, Srgba::new(1.0, 0.0, 0.0, 1.0)),
+            ],
+        )
+        .into(),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("gradients.png")
+        .unwrap();
+}
@@ -3,7 +3,8 @@ use ab_glyph::FontArc;
 use image::ImageReader;
 use palette::Srgba;
 use render_agnostic::{
-    ImageRenderer, Renderer, image_registries::image_image_registry::ImageImageRegistry,
+    ImageRenderer, Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry,
 };
 
 fn main() {
@@ -26,6 +27,7 @@ fn main() {
         2,
         FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
         ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
     );
 
     image_renderer.render_rectangle(
@@ -34,7 +36,7 @@ fn main() {
         100.0,
         dvec2(0.0, 0.0),
         0.0,
-        Srgba::new(1.0, 0.0, 0.0, 1.0),
+        Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
     );
 
     image_renderer.render_image("beebo", dvec2(0.0, 0.0), 100.0, 100.0, dvec2(0.0, 0.0), 0.0);
@@ -45,7 +47,7 @@ fn main() {
         100.0,
         dvec2(0.0, 0.0),
         45.0f64.to_radians(),
-        Srgba::new(1.0, 0.0, 0.0, 1.0),
+        Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
     );
 
     image_renderer.render_image(
@@ -63,7 +65,7 @@ fn main() {
         100.0,
         dvec2(0.5, 0.5),
         -45.0f64.to_radians(),
-        Srgba::new(1.0, 0.0, 0.0, 1.0),
+        Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
     );
 
     image_renderer.render_image(
@@ -81,7 +83,7 @@ fn main() {
         100.0,
         dvec2(1.0, 1.0),
         -90.0f64.to_radians(),
-        Srgba::new(1.0, 0.0, 0.0, 1.0),
+        Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
     );
 
     image_renderer.render_image(
@@ -93,9 +95,21 @@ fn main() {
         -90.0f64.to_radians(),
     );
 
-    image_renderer.render_circle(dvec2(100.0, 100.0), 10.0, Srgba::new(1.0, 1.0, 0.0, 1.0));
-    image_renderer.render_circle(dvec2(200.0, 200.0), 10.0, Srgba::new(1.0, 1.0, 0.0, 1.0));
-    image_renderer.render_circle(dvec2(300.0, 300.0), 10.0, Srgba::new(1.0, 1.0, 0.0, 1.0));
+    image_renderer.render_circle(
+        dvec2(100.0, 100.0),
+        10.0,
+        Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+    );
+    image_renderer.render_circle(
+        dvec2(200.0, 200.0),
+        10.0,
+        Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+    );
+    image_renderer.render_circle(
+        dvec2(300.0, 300.0),
+        10.0,
+        Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+    );
 
     image_renderer
         .render_image_onto(image_renderer.black())
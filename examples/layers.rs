@@ -0,0 +1,70 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    BlendMode, Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn render_character(renderer: &mut impl Renderer, position: DVec2, alpha: f32) {
+    let body = Srgba::new(1.0, 0.6, 0.0, alpha);
+    let eye = Srgba::new(1.0, 1.0, 1.0, alpha);
+
+    renderer.render_circle(position, 16.0, body.into());
+    renderer.render_rectangle(position, 32.0, 24.0, dvec2(0.5, 0.0), 0.0, body.into());
+    renderer.render_circle(position + dvec2(-6.0, -2.0), 5.0, eye.into());
+    renderer.render_circle(position + dvec2(6.0, -2.0), 5.0, eye.into());
+}
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    for i in 0..8 {
+        image_renderer.render_rectangle(
+            dvec2(0.0, 16.0 * i as f64),
+            128.0,
+            8.0,
+            DVec2::ZERO,
+            0.0,
+            Srgba::new(0.2, 0.2, 0.6, 1.0).into(),
+        );
+    }
+
+    render_character(&mut image_renderer, dvec2(32.0, 32.0), 0.5);
+
+    image_renderer.begin_layer(0.5, BlendMode::Normal);
+    render_character(&mut image_renderer, dvec2(96.0, 32.0), 1.0);
+    image_renderer.end_layer();
+
+    image_renderer.begin_layer(0.75, BlendMode::Additive);
+    render_character(&mut image_renderer, dvec2(32.0, 88.0), 1.0);
+    image_renderer.end_layer();
+
+    image_renderer.begin_layer(1.0, BlendMode::Normal);
+    render_character(&mut image_renderer, dvec2(96.0, 88.0), 1.0);
+    image_renderer.set_blend_mode(BlendMode::Erase);
+    image_renderer.render_rectangle(
+        dvec2(80.0, 96.0),
+        32.0,
+        4.0,
+        DVec2::ZERO,
+        0.0,
+        Srgba::new(1.0, 1.0, 1.0, 1.0).into(),
+    );
+    image_renderer.set_blend_mode(BlendMode::Normal);
+    image_renderer.end_layer();
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("layers.png")
+        .unwrap();
+}
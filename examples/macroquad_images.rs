@@ -5,7 +5,8 @@ use macroquad::{
 };
 use palette::Srgba;
 use render_agnostic::{
-    MacroquadRenderer, Renderer, image_registries::macroquad_image_registry::MacroquadImageRegistry,
+    MacroquadRenderer, Renderer, font_registries::macroquad_font_registry::MacroquadFontRegistry,
+    image_registries::macroquad_image_registry::MacroquadImageRegistry,
 };
 
 #[macroquad::main("Images")]
@@ -17,7 +18,11 @@ async fn main() {
         Texture2D::from_image(&load_image("BeeboBall.png").await.unwrap()),
     );
 
-    let mut macroquad_renderer = MacroquadRenderer::new(None, macroquad_image_registry);
+    let mut macroquad_renderer = MacroquadRenderer::new(
+        None,
+        macroquad_image_registry,
+        MacroquadFontRegistry::default(),
+    );
 
     loop {
         clear_background(BLACK);
@@ -28,7 +33,7 @@ async fn main() {
             100.0,
             dvec2(0.0, 0.0),
             0.0,
-            Srgba::new(1.0, 0.0, 0.0, 1.0),
+            Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
         );
 
         macroquad_renderer.render_image(
@@ -46,7 +51,7 @@ async fn main() {
             100.0,
             dvec2(0.0, 0.0),
             45.0f64.to_radians(),
-            Srgba::new(1.0, 0.0, 0.0, 1.0),
+            Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
         );
 
         macroquad_renderer.render_image(
@@ -64,7 +69,7 @@ async fn main() {
             100.0,
             dvec2(0.5, 0.5),
             -45.0f64.to_radians(),
-            Srgba::new(1.0, 0.0, 0.0, 1.0),
+            Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
         );
 
         macroquad_renderer.render_image(
@@ -82,7 +87,7 @@ async fn main() {
             100.0,
             dvec2(1.0, 1.0),
             -90.0f64.to_radians(),
-            Srgba::new(1.0, 0.0, 0.0, 1.0),
+            Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
         );
 
         macroquad_renderer.render_image(
@@ -94,9 +99,21 @@ async fn main() {
             -90.0f64.to_radians(),
         );
 
-        macroquad_renderer.render_circle(dvec2(100.0, 100.0), 10.0, Srgba::new(1.0, 1.0, 0.0, 1.0));
-        macroquad_renderer.render_circle(dvec2(200.0, 200.0), 10.0, Srgba::new(1.0, 1.0, 0.0, 1.0));
-        macroquad_renderer.render_circle(dvec2(300.0, 300.0), 10.0, Srgba::new(1.0, 1.0, 0.0, 1.0));
+        macroquad_renderer.render_circle(
+            dvec2(100.0, 100.0),
+            10.0,
+            Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+        );
+        macroquad_renderer.render_circle(
+            dvec2(200.0, 200.0),
+            10.0,
+            Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+        );
+        macroquad_renderer.render_circle(
+            dvec2(300.0, 300.0),
+            10.0,
+            Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+        );
 
         next_frame().await
     }
@@ -0,0 +1,68 @@
+use ::glam::{DVec2, dvec2};
+use ab_glyph::FontArc;
+use image::ImageReader;
+use palette::Srgba;
+use render_agnostic::{
+    ImageRenderer, MeshVertex, Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry,
+};
+
+fn main() {
+    let mut image_image_registry = ImageImageRegistry::default();
+
+    image_image_registry.register_image(
+        String::from("beebo"),
+        ImageReader::open("Beebo.png")
+            .unwrap()
+            .decode()
+            .unwrap()
+            .into_rgba8(),
+    );
+
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        64,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        image_image_registry,
+        ImageFontRegistry::default(),
+    );
+
+    let gradient = [
+        MeshVertex::new(
+            dvec2(32.0, 4.0),
+            DVec2::ZERO,
+            Srgba::new(1.0, 0.0, 0.0, 1.0),
+        ),
+        MeshVertex::new(
+            dvec2(60.0, 60.0),
+            DVec2::ZERO,
+            Srgba::new(0.0, 1.0, 0.0, 1.0),
+        ),
+        MeshVertex::new(
+            dvec2(4.0, 60.0),
+            DVec2::ZERO,
+            Srgba::new(0.0, 0.0, 1.0, 1.0),
+        ),
+    ];
+
+    image_renderer.render_mesh(&gradient, &[0, 1, 2], None);
+
+    let white = Srgba::new(1.0, 1.0, 1.0, 1.0);
+
+    let skewed_quad = [
+        MeshVertex::new(dvec2(76.0, 4.0), dvec2(0.0, 0.0), white),
+        MeshVertex::new(dvec2(124.0, 12.0), dvec2(1.0, 0.0), white),
+        MeshVertex::new(dvec2(116.0, 60.0), dvec2(1.0, 1.0), white),
+        MeshVertex::new(dvec2(68.0, 52.0), dvec2(0.0, 1.0), white),
+    ];
+
+    image_renderer.render_mesh(&skewed_quad, &[0, 1, 2, 0, 2, 3], Some("beebo"));
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("meshes.png")
+        .unwrap();
+}
@@ -0,0 +1,82 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    FillRule, LineCap, LineJoin, Path, Renderer,
+    font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        2.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let mut heart = Path::new();
+
+    heart
+        .move_to(dvec2(16.0, 28.0))
+        .cubic_to(dvec2(0.0, 16.0), dvec2(8.0, 0.0), dvec2(16.0, 8.0))
+        .cubic_to(dvec2(24.0, 0.0), dvec2(32.0, 16.0), dvec2(16.0, 28.0))
+        .close();
+
+    image_renderer.render_path_fill(
+        &heart,
+        FillRule::NonZero,
+        Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
+    );
+
+    image_renderer.render_path_stroke(
+        &heart,
+        1.0,
+        LineJoin::Round,
+        LineCap::Butt,
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    let mut rings = Path::new();
+
+    rings
+        .move_to(dvec2(36.0, 16.0))
+        .arc_to(DVec2::splat(12.0), 0.0, false, true, dvec2(60.0, 16.0))
+        .arc_to(DVec2::splat(12.0), 0.0, false, true, dvec2(36.0, 16.0))
+        .close()
+        .move_to(dvec2(42.0, 16.0))
+        .arc_to(DVec2::splat(6.0), 0.0, false, true, dvec2(54.0, 16.0))
+        .arc_to(DVec2::splat(6.0), 0.0, false, true, dvec2(42.0, 16.0))
+        .close();
+
+    image_renderer.render_path_fill(
+        &rings,
+        FillRule::EvenOdd,
+        Srgba::new(0.0, 0.0, 1.0, 1.0).into(),
+    );
+
+    let mut wave = Path::new();
+
+    wave.move_to(dvec2(4.0, 52.0))
+        .quad_to(dvec2(12.0, 36.0), dvec2(20.0, 52.0))
+        .quad_to(dvec2(28.0, 68.0), dvec2(36.0, 52.0))
+        .quad_to(dvec2(44.0, 36.0), dvec2(52.0, 52.0))
+        .quad_to(dvec2(60.0, 68.0), dvec2(62.0, 52.0));
+
+    image_renderer.render_path_stroke(
+        &wave,
+        2.0,
+        LineJoin::Round,
+        LineCap::Round,
+        Srgba::new(0.0, 1.0, 0.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("paths.png")
+        .unwrap();
+}
@@ -2,8 +2,8 @@ use ab_glyph::FontArc;
 use glam::{DVec2, dvec2};
 use palette::Srgba;
 use render_agnostic::{
-    Renderer, image_registries::image_image_registry::ImageImageRegistry,
-    renderers::image::ImageRenderer,
+    Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
 };
 
 fn main() {
@@ -15,6 +15,7 @@ fn main() {
         4,
         FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
         ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
     );
 
     image_renderer.render_point(dvec2(1.0, 1.0), Srgba::new(1.0, 0.0, 0.0, 1.0));
@@ -0,0 +1,55 @@
+use std::f64::consts::PI;
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        64,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let hexagon = (0..6)
+        .map(|i| dvec2(32.0, 32.0) + 24.0 * DVec2::from_angle(i as f64 * PI / 3.0))
+        .collect::<Vec<DVec2>>();
+
+    let holes = vec![
+        (0..6)
+            .map(|i| dvec2(32.0, 32.0) + 12.0 * DVec2::from_angle(i as f64 * PI / 3.0))
+            .collect::<Vec<DVec2>>(),
+    ];
+
+    image_renderer.render_polygon(&hexagon, &holes, Srgba::new(1.0, 1.0, 1.0, 1.0).into());
+
+    image_renderer.render_polygon_lines(&hexagon, &holes, 2.0, Srgba::new(1.0, 0.0, 0.0, 1.0));
+
+    let arrow = vec![
+        dvec2(72.0, 24.0),
+        dvec2(100.0, 24.0),
+        dvec2(100.0, 12.0),
+        dvec2(120.0, 32.0),
+        dvec2(100.0, 52.0),
+        dvec2(100.0, 40.0),
+        dvec2(72.0, 40.0),
+    ];
+
+    image_renderer.render_polygon(&arrow, &[], Srgba::new(0.0, 0.0, 1.0, 1.0).into());
+
+    image_renderer.render_polygon_lines(&arrow, &[], 2.0, Srgba::new(0.0, 1.0, 0.0, 1.0));
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("polygons.png")
+        .unwrap();
+}
@@ -0,0 +1,59 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    LineCap, LineJoin, Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let zigzag = |y: f64| {
+        vec![
+            dvec2(16.0, y + 16.0),
+            dvec2(40.0, y),
+            dvec2(64.0, y + 16.0),
+            dvec2(88.0, y),
+            dvec2(112.0, y + 16.0),
+        ]
+    };
+
+    image_renderer.render_polyline(
+        &zigzag(8.0),
+        8.0,
+        LineJoin::Miter(4.0),
+        LineCap::Butt,
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.render_polyline(
+        &zigzag(48.0),
+        8.0,
+        LineJoin::Round,
+        LineCap::Round,
+        Srgba::new(1.0, 0.0, 0.0, 1.0),
+    );
+
+    image_renderer.render_polyline(
+        &zigzag(88.0),
+        8.0,
+        LineJoin::Bevel,
+        LineCap::Square,
+        Srgba::new(0.0, 0.0, 1.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("polylines.png")
+        .unwrap();
+}
@@ -4,8 +4,8 @@ use ab_glyph::FontArc;
 use glam::{DVec2, dvec2};
 use palette::Srgba;
 use render_agnostic::{
-    Renderer, image_registries::image_image_registry::ImageImageRegistry,
-    renderers::image::ImageRenderer,
+    Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
 };
 
 fn main() {
@@ -17,6 +17,7 @@ fn main() {
         1,
         FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
         ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
     );
 
     image_renderer.render_rectangle(
@@ -25,7 +26,7 @@ fn main() {
         16.0,
         DVec2::splat(0.0),
         0.0,
-        Srgba::new(1.0, 1.0, 1.0, 1.0),
+        Srgba::new(1.0, 1.0, 1.0, 1.0).into(),
     );
 
     image_renderer.render_rectangle(
@@ -34,7 +35,7 @@ fn main() {
         16.0,
         DVec2::splat(0.5),
         -PI / 2.0,
-        Srgba::new(1.0, 0.0, 1.0, 1.0),
+        Srgba::new(1.0, 0.0, 1.0, 1.0).into(),
     );
 
     image_renderer.render_rectangle_lines(
@@ -0,0 +1,63 @@
+use std::f64::consts::FRAC_PI_2;
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.render_regular_polygon(
+        dvec2(32.0, 32.0),
+        24.0,
+        0.0,
+        6,
+        Srgba::new(1.0, 1.0, 1.0, 1.0).into(),
+    );
+
+    image_renderer.render_regular_polygon_lines(
+        dvec2(96.0, 32.0),
+        24.0,
+        -FRAC_PI_2,
+        5,
+        4.0,
+        Srgba::new(1.0, 0.0, 0.0, 1.0),
+    );
+
+    image_renderer.render_star(
+        dvec2(32.0, 96.0),
+        24.0,
+        10.0,
+        -FRAC_PI_2,
+        5,
+        Srgba::new(1.0, 1.0, 0.0, 1.0).into(),
+    );
+
+    image_renderer.render_star_lines(
+        dvec2(96.0, 96.0),
+        24.0,
+        16.0,
+        0.0,
+        8,
+        2.0,
+        Srgba::new(0.0, 1.0, 0.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("regular_polygons.png")
+        .unwrap();
+}
@@ -0,0 +1,88 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextSpan, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_font_registry = ImageFontRegistry::default();
+
+    image_font_registry
+        .load_font(
+            String::from("Sans"),
+            include_bytes!("dejavu_sans.ttf").to_vec(),
+        )
+        .unwrap();
+
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        image_font_registry,
+    );
+
+    let white = Srgba::new(1.0, 1.0, 1.0, 1.0);
+
+    let spans = TextSpan::from_markup(
+        "HP: [font=Sans][size=20][color=#ff4040]42[/color][/size][/font]/100",
+        white,
+        14.0,
+        &TextStyle::default(),
+    );
+    let metrics = image_renderer.measure_rich_text(&spans);
+
+    image_renderer.render_rectangle_lines(
+        dvec2(64.0, 32.0),
+        metrics.width,
+        metrics.height,
+        dvec2(0.5, 0.5),
+        0.0,
+        1.0,
+        Srgba::new(0.3, 0.3, 0.6, 1.0),
+    );
+
+    image_renderer.render_rich_text(&spans, dvec2(64.0, 32.0), anchor2d::CGC);
+
+    image_renderer.render_rich_text(
+        &[
+            TextSpan::new(
+                String::from("Small "),
+                white,
+                10.0,
+                TextStyle::default(),
+                false,
+            ),
+            TextSpan::new(
+                String::from("Big"),
+                Srgba::new(0.4, 0.8, 1.0, 1.0),
+                24.0,
+                TextStyle::default(),
+                true,
+            ),
+        ],
+        dvec2(64.0, 80.0),
+        anchor2d::CGC,
+    );
+
+    image_renderer.render_rich_text(
+        &TextSpan::from_markup(
+            "[u]Plain[/u] [[tags] [b]",
+            Srgba::new(1.0, 0.8, 0.4, 1.0),
+            12.0,
+            &TextStyle::default(),
+        ),
+        dvec2(64.0, 112.0),
+        anchor2d::CGC,
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("rich_text.png")
+        .unwrap();
+}
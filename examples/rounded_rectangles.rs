@@ -0,0 +1,69 @@
+use std::f64::consts::PI;
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    CornerRadii, Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.render_rounded_rectangle(
+        dvec2(8.0, 8.0),
+        48.0,
+        32.0,
+        DVec2::ZERO,
+        0.0,
+        CornerRadii::uniform(8.0),
+        Srgba::new(1.0, 1.0, 1.0, 1.0).into(),
+    );
+
+    image_renderer.render_rounded_rectangle_lines(
+        dvec2(96.0, 24.0),
+        48.0,
+        32.0,
+        DVec2::splat(0.5),
+        PI / 8.0,
+        CornerRadii::new(16.0, 0.0, 16.0, 0.0),
+        3.0,
+        Srgba::new(1.0, 0.0, 0.0, 1.0),
+    );
+
+    image_renderer.render_rounded_rectangle(
+        dvec2(32.0, 96.0),
+        48.0,
+        32.0,
+        DVec2::splat(0.5),
+        -PI / 8.0,
+        CornerRadii::new(0.0, 8.0, 24.0, 4.0),
+        Srgba::new(0.0, 0.0, 1.0, 1.0).into(),
+    );
+
+    image_renderer.render_rounded_rectangle_lines(
+        dvec2(120.0, 120.0),
+        48.0,
+        32.0,
+        DVec2::ONE,
+        0.0,
+        CornerRadii::uniform(100.0),
+        2.0,
+        Srgba::new(0.0, 1.0, 0.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("rounded_rectangles.png")
+        .unwrap();
+}
@@ -0,0 +1,63 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_font_registry = ImageFontRegistry::default();
+
+    image_font_registry
+        .load_font(
+            String::from("DejaVu Sans"),
+            include_bytes!("dejavu_sans.ttf").to_vec(),
+        )
+        .unwrap();
+
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        image_font_registry,
+    );
+
+    let style = TextStyle {
+        fallback_font_names: vec![String::from("DejaVu Sans")],
+        ..TextStyle::baseline()
+    };
+
+    for (i, text) in ["AVATAR", "Type Wave", "fluffy office", "مرحبا office"]
+        .into_iter()
+        .enumerate()
+    {
+        let position = dvec2(8.0, 24.0 + 28.0 * i as f64);
+        let metrics = image_renderer.measure_text(text, 20.0, &style);
+
+        image_renderer.render_line(
+            position,
+            position + DVec2::X * metrics.width,
+            1.0,
+            Srgba::new(1.0, 0.0, 0.0, 1.0),
+        );
+
+        image_renderer.render_text(
+            text,
+            position,
+            anchor2d::LGT,
+            20.0,
+            &style,
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+    }
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("shaping.png")
+        .unwrap();
+}
@@ -2,8 +2,8 @@ use ab_glyph::FontArc;
 use glam::{DVec2, dvec2};
 use palette::Srgba;
 use render_agnostic::{
-    Renderer, image_registries::image_image_registry::ImageImageRegistry,
-    renderers::image::ImageRenderer,
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
 };
 
 fn main() {
@@ -15,6 +15,7 @@ fn main() {
         4,
         FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
         ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
     );
 
     image_renderer.render_text(
@@ -22,6 +23,7 @@ fn main() {
         dvec2(0.0, 0.0),
         anchor2d::LGT,
         24.0,
+        &TextStyle::default(),
         Srgba::new(1.0, 1.0, 1.0, 1.0),
     );
 
@@ -30,6 +32,7 @@ fn main() {
         dvec2(64.0, 64.0),
         anchor2d::CGC,
         24.0,
+        &TextStyle::default(),
         1.0,
         Srgba::new(1.0, 1.0, 1.0, 1.0),
         Srgba::new(1.0, 0.0, 0.0, 1.0),
@@ -40,6 +43,7 @@ fn main() {
         dvec2(128.0, 128.0),
         anchor2d::RGB,
         24.0,
+        &TextStyle::default(),
         Srgba::new(1.0, 1.0, 1.0, 1.0),
     );
 
@@ -48,6 +52,7 @@ fn main() {
         dvec2(32.0, 32.0),
         anchor2d::CGC,
         24.0,
+        &TextStyle::default(),
         Srgba::new(1.0, 0.0, 0.0, 1.0),
     );
 
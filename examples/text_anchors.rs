@@ -0,0 +1,73 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let rows = [
+        (anchor2d::LGT, TextStyle::default()),
+        (anchor2d::LGC, TextStyle::default()),
+        (anchor2d::LGB, TextStyle::default()),
+        (anchor2d::LGT, TextStyle::baseline()),
+    ];
+
+    for (i, (anchor, style)) in rows.iter().enumerate() {
+        let y = 20.0 + 28.0 * i as f64;
+
+        image_renderer.render_line(
+            dvec2(0.0, y),
+            dvec2(128.0, y),
+            0.5,
+            Srgba::new(1.0, 0.0, 0.0, 1.0),
+        );
+
+        image_renderer.render_text(
+            "Hxgy",
+            dvec2(8.0, y),
+            *anchor,
+            20.0,
+            style,
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+    }
+
+    let metrics = image_renderer.measure_text("Hxgy", 20.0, &TextStyle::default());
+
+    image_renderer.render_rectangle_lines(
+        dvec2(72.0, 48.0),
+        metrics.width,
+        metrics.cap_height,
+        dvec2(0.0, 0.5),
+        0.0,
+        0.5,
+        Srgba::new(0.0, 1.0, 0.0, 1.0),
+    );
+
+    image_renderer.render_text(
+        "Hxgy",
+        dvec2(72.0, 48.0),
+        anchor2d::LGC,
+        20.0,
+        &TextStyle::default(),
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("text_anchors.png")
+        .unwrap();
+}
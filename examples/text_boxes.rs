@@ -0,0 +1,65 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextAlign, TextBox, TextOverflow, TextStyle,
+    font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+const TEXT: &str = "The quick brown fox jumps over the lazy dog.\nPack my box.";
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let text_boxes = [
+        TextBox::wrapped(56.0),
+        TextBox::new(56.0, None, TextAlign::Right, 1.0, TextOverflow::Visible),
+        TextBox::new(56.0, None, TextAlign::Justify, 1.2, TextOverflow::Visible),
+        TextBox::new(
+            56.0,
+            Some(32.0),
+            TextAlign::Center,
+            1.0,
+            TextOverflow::Ellipsis,
+        ),
+    ];
+
+    for (i, text_box) in text_boxes.iter().enumerate() {
+        let position = dvec2(4.0 + 64.0 * (i % 2) as f64, 4.0 + 64.0 * (i / 2) as f64);
+
+        image_renderer.render_rectangle_lines(
+            position,
+            text_box.width,
+            text_box.height.unwrap_or(56.0),
+            DVec2::ZERO,
+            0.0,
+            1.0,
+            Srgba::new(0.3, 0.3, 0.6, 1.0),
+        );
+
+        image_renderer.render_text_box(
+            TEXT,
+            position,
+            anchor2d::LGT,
+            10.0,
+            &TextStyle::default(),
+            text_box,
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+    }
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("text_boxes.png")
+        .unwrap();
+}
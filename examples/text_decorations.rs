@@ -0,0 +1,78 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextBackground, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let white = Srgba::new(1.0, 1.0, 1.0, 1.0);
+
+    image_renderer.render_text(
+        "Underline",
+        dvec2(64.0, 16.0),
+        anchor2d::CGC,
+        14.0,
+        &TextStyle {
+            underline: true,
+            ..TextStyle::default()
+        },
+        white,
+    );
+
+    image_renderer.render_text(
+        "Strikethrough",
+        dvec2(64.0, 40.0),
+        anchor2d::CGC,
+        14.0,
+        &TextStyle {
+            strikethrough: true,
+            ..TextStyle::default()
+        },
+        white,
+    );
+
+    image_renderer.render_text(
+        "TRACKING",
+        dvec2(64.0, 64.0),
+        anchor2d::CGC,
+        12.0,
+        &TextStyle {
+            letter_spacing: 3.0,
+            ..TextStyle::default()
+        },
+        Srgba::new(1.0, 0.8, 0.4, 1.0),
+    );
+
+    image_renderer.render_text_outline(
+        "Boxed and\nunderlined",
+        dvec2(64.0, 100.0),
+        anchor2d::CGC,
+        12.0,
+        &TextStyle {
+            underline: true,
+            background: Some(TextBackground::new(Srgba::new(0.2, 0.3, 0.6, 1.0), 3.0)),
+            ..TextStyle::default()
+        },
+        1.0,
+        white,
+        Srgba::new(0.0, 0.0, 0.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("text_decorations.png")
+        .unwrap();
+}
@@ -0,0 +1,67 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let text = "Measured\ntext box";
+    let size = 20.0;
+    let position = dvec2(16.0, 24.0);
+
+    let metrics = image_renderer.measure_text(text, size, &TextStyle::default());
+
+    image_renderer.render_rectangle(
+        position,
+        metrics.width,
+        metrics.height,
+        DVec2::ZERO,
+        0.0,
+        Srgba::new(0.2, 0.2, 0.6, 1.0).into(),
+    );
+
+    for (i, line_width) in metrics.line_widths.iter().copied().enumerate() {
+        image_renderer.render_line(
+            position + dvec2(0.0, metrics.ascent + size * i as f64),
+            position + dvec2(line_width, metrics.ascent + size * i as f64),
+            1.0,
+            Srgba::new(1.0, 0.0, 0.0, 1.0),
+        );
+    }
+
+    image_renderer.render_text(
+        text,
+        position,
+        anchor2d::LGT,
+        size,
+        &TextStyle::default(),
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.render_text(
+        &format!("{} lines", metrics.get_line_count()),
+        dvec2(64.0, 104.0),
+        anchor2d::CGC,
+        12.0,
+        &TextStyle::default(),
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("text_metrics.png")
+        .unwrap();
+}
@@ -0,0 +1,60 @@
+use ab_glyph::FontArc;
+use glam::{DAffine2, DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.render_text_outline(
+        "Thick",
+        dvec2(64.0, 24.0),
+        anchor2d::CGC,
+        28.0,
+        &TextStyle::default(),
+        4.0,
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+        Srgba::new(0.9, 0.2, 0.2, 1.0),
+    );
+
+    image_renderer.render_text_outline(
+        "Translucent",
+        dvec2(64.0, 64.0),
+        anchor2d::CGC,
+        20.0,
+        &TextStyle::default(),
+        2.0,
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+        Srgba::new(0.2, 0.6, 1.0, 0.5),
+    );
+
+    image_renderer.push_transform(DAffine2::from_angle_translation(-0.2, dvec2(64.0, 104.0)));
+    image_renderer.render_text_outline(
+        "Rotated",
+        DVec2::ZERO,
+        anchor2d::CGC,
+        20.0,
+        &TextStyle::default(),
+        2.0,
+        Srgba::new(0.0, 0.0, 0.0, 1.0),
+        Srgba::new(1.0, 0.8, 0.4, 1.0),
+    );
+    image_renderer.pop_transform();
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("text_outlines.png")
+        .unwrap();
+}
@@ -0,0 +1,76 @@
+use std::f64::consts::PI;
+
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    LineCap, LineJoin, Path, Renderer, TextAlign, TextPath, TextStyle,
+    font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let guide_color = Srgba::new(0.3, 0.3, 0.6, 1.0);
+
+    image_renderer.render_arc_lines(dvec2(64.0, 56.0), 40.0, PI, 64, PI, 1.0, guide_color);
+
+    image_renderer.render_text_on_path(
+        "Circular gauge label",
+        &Path::circular_arc(dvec2(64.0, 56.0), 40.0, PI, PI),
+        10.0,
+        &TextStyle::default(),
+        &TextPath::new(0.0, TextAlign::Center),
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    let road = [
+        dvec2(8.0, 120.0),
+        dvec2(40.0, 96.0),
+        dvec2(72.0, 112.0),
+        dvec2(120.0, 80.0),
+    ];
+
+    image_renderer.render_polyline(&road, 1.0, LineJoin::Round, LineCap::Round, guide_color);
+
+    image_renderer.render_text_on_path(
+        "Main Street",
+        &Path::polyline(&road),
+        10.0,
+        &TextStyle::default(),
+        &TextPath::new(0.0, TextAlign::Justify),
+        Srgba::new(1.0, 0.8, 0.4, 1.0),
+    );
+
+    let mut curve = Path::new();
+
+    curve.move_to(dvec2(8.0, 72.0)).cubic_to(
+        dvec2(40.0, 40.0),
+        dvec2(88.0, 104.0),
+        dvec2(120.0, 72.0),
+    );
+
+    image_renderer.render_text_on_path(
+        "Bézier curve",
+        &curve,
+        10.0,
+        &TextStyle::default(),
+        &TextPath::new(8.0, TextAlign::Left),
+        Srgba::new(0.4, 0.8, 1.0, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("text_paths.png")
+        .unwrap();
+}
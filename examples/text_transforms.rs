@@ -0,0 +1,80 @@
+use std::f64::consts::FRAC_PI_2;
+
+use ab_glyph::FontArc;
+use glam::{DMat2, DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    LineCap, LineJoin, Renderer, TextStyle,
+    font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    let white = Srgba::new(1.0, 1.0, 1.0, 1.0);
+    let axis_color = Srgba::new(0.3, 0.3, 0.6, 1.0);
+
+    image_renderer.render_polyline(
+        &[dvec2(24.0, 8.0), dvec2(24.0, 72.0), dvec2(120.0, 72.0)],
+        1.0,
+        LineJoin::Bevel,
+        LineCap::Butt,
+        axis_color,
+    );
+
+    image_renderer.render_text(
+        "Vertical axis",
+        dvec2(16.0, 40.0),
+        anchor2d::CGC,
+        10.0,
+        &TextStyle::rotated(-FRAC_PI_2),
+        white,
+    );
+
+    image_renderer.render_text(
+        "Diagonal",
+        dvec2(72.0, 40.0),
+        anchor2d::CGC,
+        14.0,
+        &TextStyle::rotated(-0.4),
+        Srgba::new(1.0, 0.8, 0.4, 1.0),
+    );
+
+    image_renderer.render_text(
+        "Faux italic",
+        dvec2(64.0, 92.0),
+        anchor2d::CGC,
+        14.0,
+        &TextStyle::skewed(0.25),
+        white,
+    );
+
+    image_renderer.render_text_outline(
+        "Wide",
+        dvec2(64.0, 116.0),
+        anchor2d::CGC,
+        12.0,
+        &TextStyle {
+            transform: DMat2::from_diagonal(dvec2(2.0, 1.0)),
+            ..TextStyle::default()
+        },
+        1.0,
+        Srgba::new(0.4, 0.8, 1.0, 1.0),
+        Srgba::new(0.1, 0.2, 0.4, 1.0),
+    );
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("text_transforms.png")
+        .unwrap();
+}
@@ -0,0 +1,97 @@
+use std::f64::consts::{FRAC_PI_4, TAU};
+
+use ab_glyph::FontArc;
+use glam::{DAffine2, DMat2, DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn render_arm(renderer: &mut impl Renderer, depth: u32) {
+    renderer.render_rectangle(
+        DVec2::ZERO,
+        24.0,
+        6.0,
+        dvec2(0.0, 0.5),
+        0.0,
+        Srgba::new(1.0, 1.0 - depth as f64 as f32 * 0.3, 0.0, 1.0).into(),
+    );
+
+    renderer.render_circle(DVec2::ZERO, 4.0, Srgba::new(1.0, 1.0, 1.0, 1.0).into());
+
+    if depth < 3 {
+        renderer.push_translation(dvec2(24.0, 0.0));
+        renderer.push_rotation(FRAC_PI_4);
+        renderer.push_scale(DVec2::splat(0.75));
+
+        render_arm(renderer, depth + 1);
+
+        renderer.pop_transform();
+        renderer.pop_transform();
+        renderer.pop_transform();
+    }
+}
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.push_translation(dvec2(16.0, 64.0));
+    image_renderer.push_rotation(-FRAC_PI_4);
+
+    render_arm(&mut image_renderer, 0);
+
+    image_renderer.pop_transform();
+    image_renderer.pop_transform();
+
+    image_renderer.push_transform(DAffine2::from_mat2_translation(
+        DMat2::from_cols(dvec2(1.0, 0.0), dvec2(-0.5, 1.0)),
+        dvec2(96.0, 24.0),
+    ));
+
+    image_renderer.render_circle_lines(DVec2::ZERO, 16.0, 2.0, Srgba::new(0.0, 1.0, 1.0, 1.0));
+
+    image_renderer.render_text(
+        "Skew",
+        DVec2::ZERO,
+        anchor2d::CGC,
+        12.0,
+        &TextStyle::default(),
+        Srgba::new(1.0, 1.0, 1.0, 1.0),
+    );
+
+    image_renderer.pop_transform();
+
+    image_renderer.push_translation(dvec2(88.0, 96.0));
+    image_renderer.push_scale(dvec2(2.0, 1.0));
+
+    for i in 0..8 {
+        image_renderer.push_rotation(TAU * i as f64 / 8.0);
+
+        image_renderer.render_line(
+            DVec2::ZERO,
+            dvec2(12.0, 0.0),
+            1.0,
+            Srgba::new(1.0, 0.0, 1.0, 1.0),
+        );
+
+        image_renderer.pop_transform();
+    }
+
+    image_renderer.pop_transform();
+    image_renderer.pop_transform();
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("transforms.png")
+        .unwrap();
+}
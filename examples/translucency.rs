@@ -0,0 +1,72 @@
+use ab_glyph::FontArc;
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+use render_agnostic::{
+    MeshVertex, Renderer, TextStyle, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
+};
+
+fn main() {
+    let mut image_renderer = ImageRenderer::new(
+        128,
+        128,
+        1.0,
+        DVec2::ZERO,
+        4,
+        FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
+        ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
+    );
+
+    image_renderer.render_rectangle(
+        dvec2(8.0, 8.0),
+        112.0,
+        24.0,
+        DVec2::ZERO,
+        0.0,
+        Srgba::new(1.0, 1.0, 1.0, 1.0).into(),
+    );
+
+    image_renderer.render_circle(
+        dvec2(40.0, 40.0),
+        24.0,
+        Srgba::new(1.0, 0.0, 0.0, 0.5).into(),
+    );
+    image_renderer.render_circle(
+        dvec2(64.0, 40.0),
+        24.0,
+        Srgba::new(0.0, 1.0, 0.0, 0.5).into(),
+    );
+    image_renderer.render_circle(
+        dvec2(88.0, 40.0),
+        24.0,
+        Srgba::new(0.0, 0.0, 1.0, 0.5).into(),
+    );
+
+    image_renderer.render_text_outline(
+        "Tinted",
+        dvec2(64.0, 84.0),
+        anchor2d::CGC,
+        24.0,
+        &TextStyle::default(),
+        2.0,
+        Srgba::new(1.0, 1.0, 0.0, 0.5),
+        Srgba::new(0.0, 1.0, 1.0, 0.5),
+    );
+
+    let color = Srgba::new(1.0, 0.0, 1.0, 0.5);
+
+    let quad = [
+        MeshVertex::new(dvec2(16.0, 100.0), DVec2::ZERO, color),
+        MeshVertex::new(dvec2(112.0, 100.0), DVec2::ZERO, color),
+        MeshVertex::new(dvec2(112.0, 120.0), DVec2::ZERO, color),
+        MeshVertex::new(dvec2(16.0, 120.0), DVec2::ZERO, color),
+    ];
+
+    image_renderer.render_mesh(&quad, &[0, 1, 2, 0, 2, 3], None);
+
+    image_renderer
+        .render_image_onto(image_renderer.black())
+        .save("translucency.png")
+        .unwrap();
+}
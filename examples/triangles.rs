@@ -4,8 +4,8 @@ use ab_glyph::FontArc;
 use glam::DVec2;
 use palette::Srgba;
 use render_agnostic::{
-    Renderer, image_registries::image_image_registry::ImageImageRegistry,
-    renderers::image::ImageRenderer,
+    Renderer, font_registries::image_font_registry::ImageFontRegistry,
+    image_registries::image_image_registry::ImageImageRegistry, renderers::image::ImageRenderer,
 };
 
 fn main() {
@@ -17,20 +17,21 @@ fn main() {
         4,
         FontArc::try_from_slice(include_bytes!("roboto.ttf")).unwrap(),
         ImageImageRegistry::default(),
+        ImageFontRegistry::default(),
     );
 
     image_renderer.render_equilateral_triangle(
         DVec2::splat(16.0),
         16.0,
         0.0,
-        Srgba::new(1.0, 1.0, 1.0, 1.0),
+        Srgba::new(1.0, 1.0, 1.0, 1.0).into(),
     );
 
     image_renderer.render_equilateral_triangle(
         DVec2::splat(16.0),
         16.0,
         PI,
-        Srgba::new(1.0, 0.0, 0.0, 1.0),
+        Srgba::new(1.0, 0.0, 0.0, 1.0).into(),
     );
 
     image_renderer.render_equilateral_triangle_lines(
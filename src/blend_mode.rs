@@ -0,0 +1,105 @@
+#[cfg(feature = "image")]
+use palette::Srgba;
+
+/// How drawn colors are combined with the colors already drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlendMode {
+    /// Draws over what is already drawn, as far as the drawn color is opaque.
+    #[default]
+    Normal,
+    /// Adds the drawn colors to what is already drawn.
+    Additive,
+    Multiply,
+    Screen,
+    /// Multiplies dark and screens light colors already drawn.
+    Overlay,
+    Darken,
+    Lighten,
+    /// Clears what is already drawn, as far as the drawn color is opaque.
+    Erase,
+}
+
+impl BlendMode {
+    /// Returns `source` drawn over `destination`, both with straight alpha.
+    #[cfg(feature = "image")]
+    pub(crate) fn composite(self, source: Srgba, destination: Srgba) -> Srgba {
+        let source_alpha = source.alpha;
+        let destination_alpha = destination.alpha;
+
+        let (alpha, components) = match self {
+            Self::Additive => {
+                let components = [
+                    (source.red, destination.red),
+                    (source.green, destination.green),
+                    (source.blue, destination.blue),
+                ]
+                .map(|(source_component, destination_component)| {
+                    (source_component * source_alpha + destination_component * destination_alpha)
+                        .min(1.0)
+                });
+
+                ((source_alpha + destination_alpha).min(1.0), components)
+            }
+            Self::Erase => {
+                let alpha = destination_alpha * (1.0 - source_alpha);
+
+                (
+                    alpha,
+                    [destination.red, destination.green, destination.blue]
+                        .map(|component| component * alpha),
+                )
+            }
+            _ => {
+                let components = [
+                    (source.red, destination.red),
+                    (source.green, destination.green),
+                    (source.blue, destination.blue),
+                ]
+                .map(|(source_component, destination_component)| {
+                    source_alpha * (1.0 - destination_alpha) * source_component
+                        + destination_alpha * (1.0 - source_alpha) * destination_component
+                        + source_alpha
+                            * destination_alpha
+                            * self.blend(source_component, destination_component)
+                });
+
+                (
+                    source_alpha + destination_alpha * (1.0 - source_alpha),
+                    components,
+                )
+            }
+        };
+
+        if alpha <= 0.0 {
+            return Srgba::new(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let [red, green, blue] = components.map(|component| (component / alpha).clamp(0.0, 1.0));
+
+        Srgba::new(red, green, blue, alpha)
+    }
+
+    /// Returns the color of a component where an opaque source is drawn over
+    /// an opaque destination.
+    #[cfg(feature = "image")]
+    fn blend(self, source: f32, destination: f32) -> f32 {
+        match self {
+            Self::Normal => source,
+            Self::Additive => (source + destination).min(1.0),
+            Self::Multiply => source * destination,
+            Self::Screen => source + destination - source * destination,
+            Self::Overlay => {
+                if destination <= 0.5 {
+                    2.0 * source * destination
+                } else {
+                    let destination = 2.0 * destination - 1.0;
+
+                    source + destination - source * destination
+                }
+            }
+            Self::Darken => source.min(destination),
+            Self::Lighten => source.max(destination),
+            Self::Erase => destination,
+        }
+    }
+}
@@ -0,0 +1,33 @@
+/// The radii of the four corners of a rounded rectangle, in the rectangle's
+/// own unrotated frame.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f64,
+    pub top_right: f64,
+    pub bottom_right: f64,
+    pub bottom_left: f64,
+}
+
+impl CornerRadii {
+    pub fn new(top_left: f64, top_right: f64, bottom_right: f64, bottom_left: f64) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    pub fn uniform(radius: f64) -> Self {
+        Self::new(radius, radius, radius, radius)
+    }
+
+    pub(crate) fn map(&self, f: impl Fn(f64) -> f64) -> Self {
+        Self::new(
+            f(self.top_left),
+            f(self.top_right),
+            f(self.bottom_right),
+            f(self.bottom_left),
+        )
+    }
+}
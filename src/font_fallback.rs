@@ -0,0 +1,34 @@
+/// Splits `text` into runs of characters drawn with the same font of a
+/// fallback chain of `font_count` fonts, each character with the first font
+/// `has_glyph` says has a glyph for it, or the first font when none has. Runs
+/// are returned with the index of their font.
+pub(crate) fn split_by_font(
+    text: &str,
+    font_count: usize,
+    has_glyph: impl Fn(usize, char) -> bool,
+) -> Vec<(usize, &str)> {
+    let mut runs = Vec::new();
+    let mut run_start = 0;
+    let mut run_font_index = None;
+
+    for (i, c) in text.char_indices() {
+        let font_index = (0..font_count)
+            .find(|font_index| has_glyph(*font_index, c))
+            .unwrap_or(0);
+
+        if let Some(previous_font_index) = run_font_index
+            && previous_font_index != font_index
+        {
+            runs.push((previous_font_index, &text[run_start..i]));
+            run_start = i;
+        }
+
+        run_font_index = Some(font_index);
+    }
+
+    if let Some(font_index) = run_font_index {
+        runs.push((font_index, &text[run_start..]));
+    }
+
+    runs
+}
@@ -0,0 +1,85 @@
+use anchor2d::{VerticalAnchor, VerticalAnchorContext, VerticalAnchorValue};
+
+use crate::{TextMetrics, text_style::TextAnchoring};
+
+/// The vertical metrics of a font at one size, in the coordinates text is
+/// drawn in.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub(crate) struct FontMetrics {
+    pub ascent: f64,
+    pub descent: f64,
+    pub cap_height: f64,
+}
+
+impl FontMetrics {
+    /// Reads the metrics of `font`, with `scale` converting font units to the
+    /// coordinates text is drawn in. Fonts without an `H` use their ascent as
+    /// their cap height.
+    #[cfg(any(feature = "image", feature = "macroquad"))]
+    pub fn from_font(font: &impl ab_glyph::Font, scale: f64) -> Self {
+        let ascent = font.ascent_unscaled() as f64 * scale;
+
+        // Outline bounds are in font units with `min.y` at the top.
+        let cap_height = font
+            .outline(font.glyph_id('H'))
+            .map(|outline| outline.bounds.min.y.max(outline.bounds.max.y) as f64 * scale)
+            .unwrap_or(ascent);
+
+        Self {
+            ascent,
+            descent: -font.descent_unscaled() as f64 * scale,
+            cap_height,
+        }
+    }
+
+    pub fn from_text_metrics(text_metrics: &TextMetrics) -> Self {
+        Self {
+            ascent: text_metrics.ascent,
+            descent: text_metrics.descent,
+            cap_height: text_metrics.cap_height,
+        }
+    }
+
+    pub fn scaled(&self, scale: f64) -> Self {
+        Self {
+            ascent: self.ascent * scale,
+            descent: self.descent * scale,
+            cap_height: self.cap_height * scale,
+        }
+    }
+
+    /// Returns metrics that reach as far above and below the baseline as both
+    /// these and `other`, keeping the cap height of these.
+    pub fn merged(&self, other: &Self) -> Self {
+        Self {
+            ascent: self.ascent.max(other.ascent),
+            descent: self.descent.max(other.descent),
+            cap_height: self.cap_height,
+        }
+    }
+
+    /// Returns the y of the baseline of a line anchored vertically at `y`.
+    pub fn get_baseline(&self, y: f64, anchor: VerticalAnchor, anchoring: TextAnchoring) -> f64 {
+        if anchoring == TextAnchoring::Baseline {
+            return y;
+        }
+
+        match (anchor.get_context(), anchor.get_value()) {
+            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Bottom) => y - self.descent,
+            (VerticalAnchorContext::Math, VerticalAnchorValue::Bottom) => y + self.ascent,
+            (_, VerticalAnchorValue::Center) => y + self.cap_height / 2.0,
+            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Top) => y + self.ascent,
+            (VerticalAnchorContext::Math, VerticalAnchorValue::Top) => y - self.descent,
+        }
+    }
+
+    pub fn get_text_metrics(&self, line_height: f64, line_widths: Vec<f64>) -> TextMetrics {
+        TextMetrics::new(
+            self.ascent,
+            self.descent,
+            self.cap_height,
+            line_height,
+            line_widths,
+        )
+    }
+}
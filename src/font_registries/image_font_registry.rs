@@ -0,0 +1,28 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use ab_glyph::{FontArc, InvalidFont};
+
+#[derive(Debug, Default, Clone)]
+pub struct ImageFontRegistry {
+    fonts: HashMap<String, FontArc>,
+}
+
+impl ImageFontRegistry {
+    pub fn register_font(&mut self, font_name: String, font: FontArc) {
+        self.fonts.insert(font_name, font);
+    }
+
+    /// Parses the TTF or OTF `bytes` and registers the font as `font_name`.
+    pub fn load_font(&mut self, font_name: String, bytes: Vec<u8>) -> Result<(), InvalidFont> {
+        self.register_font(font_name, FontArc::try_from_vec(bytes)?);
+
+        Ok(())
+    }
+
+    pub fn get_font<Q: Hash + Eq + ?Sized>(&self, font_name: &Q) -> Option<&FontArc>
+    where
+        String: Borrow<Q>,
+    {
+        self.fonts.get(font_name)
+    }
+}
@@ -0,0 +1,51 @@
+use std::{borrow::Borrow, collections::HashMap, hash::Hash};
+
+use ab_glyph::FontArc;
+use macroquad::{
+    Error,
+    text::{Font, load_ttf_font_from_bytes},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct MacroquadFontRegistry {
+    fonts: HashMap<String, Font>,
+    /// The parsed data of the fonts loaded from bytes, which text is measured
+    /// with.
+    font_data: HashMap<String, FontArc>,
+}
+
+impl MacroquadFontRegistry {
+    /// Registers `font` as `font_name`. Text drawn with a font registered
+    /// without its data is measured like the default font of macroquad.
+    pub fn register_font(&mut self, font_name: String, font: Font) {
+        self.font_data.remove(&font_name);
+        self.fonts.insert(font_name, font);
+    }
+
+    /// Parses the TTF or OTF `bytes` and registers the font as `font_name`.
+    pub fn load_font(&mut self, font_name: String, bytes: &[u8]) -> Result<(), Error> {
+        let font = load_ttf_font_from_bytes(bytes)?;
+
+        self.register_font(font_name.clone(), font);
+
+        if let Ok(font_data) = FontArc::try_from_vec(bytes.to_vec()) {
+            self.font_data.insert(font_name, font_data);
+        }
+
+        Ok(())
+    }
+
+    pub fn get_font<Q: Hash + Eq + ?Sized>(&self, font_name: &Q) -> Option<&Font>
+    where
+        String: Borrow<Q>,
+    {
+        self.fonts.get(font_name)
+    }
+
+    pub fn get_font_data<Q: Hash + Eq + ?Sized>(&self, font_name: &Q) -> Option<&FontArc>
+    where
+        String: Borrow<Q>,
+    {
+        self.font_data.get(font_name)
+    }
+}
@@ -0,0 +1,5 @@
+#[cfg(feature = "image")]
+pub mod image_font_registry;
+
+#[cfg(feature = "macroquad")]
+pub mod macroquad_font_registry;
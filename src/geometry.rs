@@ -0,0 +1,947 @@
+use std::{
+    f64::consts::{FRAC_PI_2, PI, TAU},
+    iter::once,
+};
+
+use glam::{DAffine2, DVec2, dvec2};
+use itertools::Itertools;
+
+use crate::{
+    corner_radii::CornerRadii,
+    path::FillRule,
+    stroke::{LineCap, LineJoin, StrokeStyle},
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Edge {
+    start: DVec2,
+    end: DVec2,
+    winding: i32,
+    region: usize,
+}
+
+impl Edge {
+    fn min_y(&self) -> f64 {
+        self.start.y.min(self.end.y)
+    }
+
+    fn max_y(&self) -> f64 {
+        self.start.y.max(self.end.y)
+    }
+
+    fn x_at(&self, y: f64) -> f64 {
+        let t = (y - self.start.y) / (self.end.y - self.start.y);
+        self.start.x + (self.end.x - self.start.x) * t
+    }
+}
+
+/// An area given by contours and the fill rule that decides what is inside
+/// them.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Region<'a> {
+    pub contours: &'a [Vec<DVec2>],
+    pub fill_rule: FillRule,
+}
+
+impl<'a> Region<'a> {
+    pub fn new(contours: &'a [Vec<DVec2>], fill_rule: FillRule) -> Self {
+        Self {
+            contours,
+            fill_rule,
+        }
+    }
+}
+
+fn get_edges(regions: &[Region]) -> Vec<Edge> {
+    regions
+        .iter()
+        .enumerate()
+        .flat_map(|(region, Region { contours, .. })| {
+            contours.iter().flat_map(move |contour| {
+                contour
+                    .iter()
+                    .copied()
+                    .zip(contour.iter().copied().cycle().skip(1))
+                    .filter(|(start, end)| start.y != end.y)
+                    .map(move |(start, end)| Edge {
+                        start,
+                        end,
+                        winding: if end.y > start.y { 1 } else { -1 },
+                        region,
+                    })
+            })
+        })
+        .collect::<Vec<Edge>>()
+}
+
+fn get_crossings(edges: &[Edge], y: f64) -> Vec<(f64, &Edge)> {
+    let mut crossings = edges
+        .iter()
+        .filter(|edge| edge.min_y() <= y && y < edge.max_y())
+        .map(|edge| (edge.x_at(y), edge))
+        .collect::<Vec<(f64, &Edge)>>();
+
+    crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    crossings
+}
+
+/// Returns the index pairs of the crossings, sorted by x, between which the
+/// points are inside every region.
+fn get_spans(crossings: &[(f64, &Edge)], regions: &[Region]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut windings = vec![0; regions.len()];
+    let mut span_start = None;
+
+    for (i, (_, edge)) in crossings.iter().enumerate() {
+        windings[edge.region] += edge.winding;
+
+        let is_inside = regions
+            .iter()
+            .zip(&windings)
+            .all(|(region, winding)| region.fill_rule.is_inside(*winding));
+
+        match (span_start, is_inside) {
+            (None, true) => span_start = Some(i),
+            (Some(start), false) => {
+                spans.push((start, i));
+                span_start = None;
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+/// Finds the horizontal spans inside contours along scanlines visited from
+/// top to bottom, keeping only the edges that reach the current scanline.
+#[cfg(feature = "image")]
+pub(crate) struct Scanner<'a> {
+    region: Region<'a>,
+    /// The edges by their top, with those before `next_edge` already active.
+    edges: Vec<Edge>,
+    next_edge: usize,
+    active_edges: Vec<Edge>,
+}
+
+#[cfg(feature = "image")]
+impl<'a> Scanner<'a> {
+    pub fn new(contours: &'a [Vec<DVec2>], fill_rule: FillRule) -> Self {
+        let region = Region::new(contours, fill_rule);
+        let mut edges = get_edges(&[region]);
+
+        edges.sort_by(|a, b| a.min_y().total_cmp(&b.min_y()));
+
+        Self {
+            region,
+            edges,
+            next_edge: 0,
+            active_edges: Vec::new(),
+        }
+    }
+
+    /// Returns the spans, as `(start_x, end_x)` pairs, that are inside the
+    /// contours along the horizontal line at `y`, which must not be above
+    /// the line of the previous call.
+    pub fn get_spans(&mut self, y: f64) -> Vec<(f64, f64)> {
+        while let Some(edge) = self.edges.get(self.next_edge)
+            && edge.min_y() <= y
+        {
+            self.active_edges.push(*edge);
+            self.next_edge += 1;
+        }
+
+        self.active_edges.retain(|edge| y < edge.max_y());
+
+        let crossings = get_crossings(&self.active_edges, y);
+
+        get_spans(&crossings, std::slice::from_ref(&self.region))
+            .into_iter()
+            .map(|(start, end)| (crossings[start].0, crossings[end].0))
+            .collect::<Vec<(f64, f64)>>()
+    }
+}
+
+/// Splits the area inside the given contours into triangles.
+#[cfg(feature = "macroquad")]
+pub(crate) fn tessellate(contours: &[Vec<DVec2>], fill_rule: FillRule) -> Vec<[DVec2; 3]> {
+    tessellate_intersection(&[Region::new(contours, fill_rule)])
+}
+
+/// Splits the area inside all of the given regions into triangles.
+///
+/// A sweep from top to bottom cuts the contours into horizontal bands at
+/// every vertex and at every crossing of neighboring edges, so no two edges
+/// cross inside a band and each filled span of a band is an exact trapezoid.
+/// This works for concave and self-intersecting contours as well as holes.
+#[cfg(feature = "macroquad")]
+pub(crate) fn tessellate_intersection(regions: &[Region]) -> Vec<[DVec2; 3]> {
+    let mut edges = get_edges(regions);
+    edges.sort_by(|a, b| a.min_y().total_cmp(&b.min_y()));
+
+    let mut vertex_ys = edges
+        .iter()
+        .flat_map(|edge| [edge.start.y, edge.end.y])
+        .collect::<Vec<f64>>();
+
+    vertex_ys.sort_by(f64::total_cmp);
+    vertex_ys.dedup();
+
+    let mut triangles = Vec::new();
+    let mut active_edges = Vec::new();
+    let mut next_edge = 0;
+
+    for (vertex_top, bottom) in vertex_ys.iter().copied().tuple_windows() {
+        while let Some(edge) = edges.get(next_edge)
+            && edge.min_y() <= vertex_top
+        {
+            active_edges.push(*edge);
+            next_edge += 1;
+        }
+
+        active_edges.retain(|edge| vertex_top < edge.max_y());
+        active_edges.sort_by(|a, b| {
+            a.x_at(vertex_top)
+                .total_cmp(&b.x_at(vertex_top))
+                .then_with(|| a.x_at(bottom).total_cmp(&b.x_at(bottom)))
+        });
+
+        let mut top = vertex_top;
+
+        while bottom - top > f64::EPSILON {
+            // Neighbors that have crossed by `top` swap places, which also
+            // fixes the order of edges that rounding put the wrong way around.
+            let mut is_sorted = false;
+
+            while !is_sorted {
+                is_sorted = true;
+
+                for i in 1..active_edges.len() {
+                    if get_crossing_y(&active_edges[i - 1], &active_edges[i], top, bottom)
+                        .is_some_and(|y| y <= top + SWEEP_TOLERANCE)
+                    {
+                        active_edges.swap(i - 1, i);
+                        is_sorted = false;
+                    }
+                }
+            }
+
+            let band_bottom = active_edges
+                .iter()
+                .tuple_windows()
+                .filter_map(|(left, right)| get_crossing_y(left, right, top, bottom))
+                .fold(bottom, f64::min);
+
+            let crossings = get_crossings(&active_edges, (top + band_bottom) / 2.0);
+
+            for (start, end) in get_spans(&crossings, regions) {
+                let left = crossings[start].1;
+                let right = crossings[end].1;
+
+                let top_left = DVec2::new(left.x_at(top), top);
+                let top_right = DVec2::new(right.x_at(top), top);
+                let bottom_left = DVec2::new(left.x_at(band_bottom), band_bottom);
+                let bottom_right = DVec2::new(right.x_at(band_bottom), band_bottom);
+
+                triangles.push([top_left, top_right, bottom_right]);
+                triangles.push([top_left, bottom_right, bottom_left]);
+            }
+
+            top = band_bottom;
+        }
+    }
+
+    triangles
+}
+
+/// How close below the top of a band a crossing can be and still count as at
+/// the top, which keeps the sweep from getting stuck on rounding errors.
+#[cfg(feature = "macroquad")]
+const SWEEP_TOLERANCE: f64 = 1e-9;
+
+/// Returns where `left` and `right` cross, or `top` if they already have, for
+/// edges that end up the wrong way around at `bottom`.
+#[cfg(feature = "macroquad")]
+fn get_crossing_y(left: &Edge, right: &Edge, top: f64, bottom: f64) -> Option<f64> {
+    let top_distance = right.x_at(top) - left.x_at(top);
+    let bottom_distance = right.x_at(bottom) - left.x_at(bottom);
+
+    if bottom_distance >= 0.0 {
+        return None;
+    }
+
+    let y = top + (bottom - top) * top_distance / (top_distance - bottom_distance);
+
+    Some(y.max(top))
+}
+
+fn get_signed_area(points: &[DVec2]) -> f64 {
+    points
+        .iter()
+        .copied()
+        .zip(points.iter().copied().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(b))
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Orients a contour so that every piece of a stroke winds the same way and
+/// the pieces can be unioned with the non-zero fill rule.
+fn get_oriented(mut points: Vec<DVec2>) -> Vec<DVec2> {
+    if get_signed_area(&points) < 0.0 {
+        points.reverse();
+    }
+
+    points
+}
+
+/// The maximum distance, in the units of the points, between a flattened
+/// curve and the true curve.
+pub(crate) const FLATTENING_TOLERANCE: f64 = 0.25;
+
+/// Returns the flattening tolerance, in local units, that keeps curves within
+/// [`FLATTENING_TOLERANCE`] after `transform`.
+pub(crate) fn get_transformed_tolerance(transform: DAffine2) -> f64 {
+    let matrix = transform.matrix2;
+    let sum_of_squares = matrix.x_axis.length_squared() + matrix.y_axis.length_squared();
+    let determinant = matrix.determinant();
+
+    let max_scale = ((sum_of_squares
+        + (sum_of_squares * sum_of_squares - 4.0 * determinant * determinant)
+            .max(0.0)
+            .sqrt())
+        / 2.0)
+        .sqrt();
+
+    if max_scale > 0.0 {
+        FLATTENING_TOLERANCE / max_scale
+    } else {
+        FLATTENING_TOLERANCE
+    }
+}
+
+/// Returns how many segments a full circle of the given radius needs to stay
+/// within `tolerance` of the true circle.
+pub(crate) fn get_circle_segments(radius: f64, tolerance: f64) -> usize {
+    if radius <= tolerance {
+        return 8;
+    }
+
+    let step = 2.0 * (1.0 - tolerance / radius).acos();
+
+    ((TAU / step).ceil() as usize).max(8)
+}
+
+/// Returns the points along an arc of the ellipse with the given `radii`
+/// rotated by `rotation`, from the ellipse's own angle `start_angle` through
+/// `arc`, including both ends.
+pub(crate) fn get_elliptical_arc_points(
+    center: DVec2,
+    radii: DVec2,
+    rotation: f64,
+    start_angle: f64,
+    arc: f64,
+    tolerance: f64,
+) -> Vec<DVec2> {
+    let axis = DVec2::from_angle(rotation);
+
+    let segments = ((get_circle_segments(radii.max_element(), tolerance) as f64 * arc.abs() / TAU)
+        .ceil() as usize)
+        .max(1);
+
+    (0..=segments)
+        .map(|i| {
+            let angle = start_angle + arc * i as f64 / segments as f64;
+            center + axis.rotate(radii * DVec2::from_angle(angle))
+        })
+        .collect::<Vec<DVec2>>()
+}
+
+/// Returns the outline of a rounded rectangle with its unrotated top left
+/// corner at `top_left`, rotated by `rotation` around `position`. Radii that
+/// don't fit are scaled down together, like in CSS.
+pub(crate) fn get_rounded_rectangle_points(
+    position: DVec2,
+    top_left: DVec2,
+    size: DVec2,
+    rotation: f64,
+    radii: CornerRadii,
+    tolerance: f64,
+) -> Vec<DVec2> {
+    let axis = DVec2::from_angle(rotation);
+    let radii = radii.map(|radius| radius.max(0.0));
+
+    let fit = [
+        size.x / (radii.top_left + radii.top_right),
+        size.x / (radii.bottom_left + radii.bottom_right),
+        size.y / (radii.top_left + radii.bottom_left),
+        size.y / (radii.top_right + radii.bottom_right),
+    ]
+    .into_iter()
+    .filter(|fit| fit.is_finite())
+    .fold(1.0, f64::min);
+
+    let radii = radii.map(|radius| radius * fit);
+
+    [
+        (top_left + DVec2::splat(radii.top_left), radii.top_left, PI),
+        (
+            top_left + dvec2(size.x - radii.top_right, radii.top_right),
+            radii.top_right,
+            3.0 * FRAC_PI_2,
+        ),
+        (
+            top_left + size - DVec2::splat(radii.bottom_right),
+            radii.bottom_right,
+            0.0,
+        ),
+        (
+            top_left + dvec2(radii.bottom_left, size.y - radii.bottom_left),
+            radii.bottom_left,
+            FRAC_PI_2,
+        ),
+    ]
+    .into_iter()
+    .flat_map(|(center, radius, start_angle)| {
+        if radius > 0.0 {
+            get_elliptical_arc_points(
+                center,
+                DVec2::splat(radius),
+                0.0,
+                start_angle,
+                FRAC_PI_2,
+                tolerance,
+            )
+        } else {
+            vec![center]
+        }
+    })
+    .map(|point| position + axis.rotate(point - position))
+    .collect::<Vec<DVec2>>()
+}
+
+/// Returns the contours of a stroke of the given thickness along the inside
+/// edge of a rounded rectangle. The contours must be filled with
+/// [`FillRule::NonZero`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn get_rounded_rectangle_lines_contours(
+    position: DVec2,
+    top_left: DVec2,
+    size: DVec2,
+    rotation: f64,
+    radii: CornerRadii,
+    thickness: f64,
+    stroke_style: &StrokeStyle,
+    tolerance: f64,
+) -> Vec<Vec<DVec2>> {
+    let get_inset_points = |inset: f64| {
+        get_rounded_rectangle_points(
+            position,
+            top_left + DVec2::splat(inset),
+            size - 2.0 * inset,
+            rotation,
+            radii.map(|radius| (radius - inset).max(0.0)),
+            tolerance,
+        )
+    };
+
+    if size.min_element() <= 2.0 * thickness {
+        return vec![get_inset_points(0.0)];
+    }
+
+    if !stroke_style.is_solid() {
+        return stroke_dashed(
+            &get_inset_points(thickness / 2.0),
+            true,
+            thickness,
+            LineJoin::default(),
+            stroke_style,
+            tolerance,
+        );
+    }
+
+    vec![
+        get_inset_points(0.0),
+        get_reversed(get_inset_points(thickness)),
+    ]
+}
+
+/// Returns the contours of a stroke of the given thickness along the inside
+/// edge of the circular arc from `start_angle` through `arc`, closing the arc
+/// through `center` unless it is a full circle. The arc has as many segments
+/// as it covers of a full circle split into `sides` segments, or into as many
+/// as keep it within `tolerance` of the true circle when `sides` is zero. The
+/// contours must be filled with [`FillRule::NonZero`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn get_arc_lines_contours(
+    center: DVec2,
+    radius: f64,
+    start_angle: f64,
+    sides: u8,
+    arc: f64,
+    thickness: f64,
+    stroke_style: &StrokeStyle,
+    tolerance: f64,
+) -> Vec<Vec<DVec2>> {
+    let is_circle = arc.abs() >= TAU;
+    let arc = arc.clamp(-TAU, TAU);
+    let circle_segments = match sides {
+        0 => get_circle_segments(radius, tolerance),
+        sides => sides as usize,
+    };
+    let segments = (circle_segments as f64 * arc.abs() / TAU).ceil().max(1.0) as usize;
+
+    let get_arc_points = |radius: f64| {
+        let mut points = (0..=segments)
+            .map(|i| {
+                center + radius * DVec2::from_angle(start_angle + arc * i as f64 / segments as f64)
+            })
+            .collect::<Vec<DVec2>>();
+
+        if is_circle {
+            points.pop();
+        }
+
+        points
+    };
+
+    if !stroke_style.is_solid() {
+        let thickness = thickness.min(radius);
+
+        return stroke_dashed(
+            &get_arc_points(radius - thickness / 2.0),
+            is_circle,
+            thickness,
+            LineJoin::default(),
+            stroke_style,
+            tolerance,
+        );
+    }
+
+    let outer = get_arc_points(radius);
+
+    match (thickness < radius, is_circle) {
+        (false, false) => vec![once(center).chain(outer).collect::<Vec<DVec2>>()],
+        (false, true) => vec![outer],
+        (true, false) => vec![
+            outer
+                .into_iter()
+                .chain(get_arc_points(radius - thickness).into_iter().rev())
+                .collect::<Vec<DVec2>>(),
+        ],
+        (true, true) => vec![outer, get_reversed(get_arc_points(radius - thickness))],
+    }
+}
+
+pub(crate) fn get_regular_polygon_points(
+    center: DVec2,
+    radius: f64,
+    rotation: f64,
+    sides: u8,
+) -> Vec<DVec2> {
+    (0..sides)
+        .map(|i| center + radius * DVec2::from_angle(rotation + TAU * i as f64 / sides as f64))
+        .collect::<Vec<DVec2>>()
+}
+
+/// Returns the outline of a star whose outer vertices are `outer_radius` from
+/// `center` and whose inner vertices are `inner_radius` from it, halfway
+/// between the outer ones.
+pub(crate) fn get_star_points(
+    center: DVec2,
+    outer_radius: f64,
+    inner_radius: f64,
+    rotation: f64,
+    points: u8,
+) -> Vec<DVec2> {
+    (0..points as usize * 2)
+        .map(|i| {
+            let radius = if i % 2 == 0 {
+                outer_radius
+            } else {
+                inner_radius
+            };
+
+            center + radius * DVec2::from_angle(rotation + PI * i as f64 / points as f64)
+        })
+        .collect::<Vec<DVec2>>()
+}
+
+/// Returns the contours of a stroke of the given thickness along the inside
+/// edge of a polygon that is star-shaped around `center`. The contours must
+/// be filled with [`FillRule::NonZero`].
+pub(crate) fn get_inset_lines_contours(
+    center: DVec2,
+    points: &[DVec2],
+    thickness: f64,
+    stroke_style: &StrokeStyle,
+    tolerance: f64,
+) -> Vec<Vec<DVec2>> {
+    let inradius = points
+        .iter()
+        .copied()
+        .zip(points.iter().copied().cycle().skip(1))
+        .map(|(start, end)| (center - start).dot((end - start).normalize().perp()).abs())
+        .fold(f64::INFINITY, f64::min);
+
+    if thickness >= inradius {
+        return vec![points.to_vec()];
+    }
+
+    if !stroke_style.is_solid() {
+        return stroke_dashed(
+            &get_inset_points(points, thickness / 2.0),
+            true,
+            thickness,
+            LineJoin::default(),
+            stroke_style,
+            tolerance,
+        );
+    }
+
+    vec![
+        points.to_vec(),
+        get_reversed(get_inset_points(points, thickness)),
+    ]
+}
+
+/// Returns the vertices of a polygon whose edges are `distance` inside the
+/// edges of the polygon through `points`.
+fn get_inset_points(points: &[DVec2], distance: f64) -> Vec<DVec2> {
+    let orientation = get_signed_area(points).signum();
+
+    let edges = points
+        .iter()
+        .copied()
+        .zip(points.iter().copied().cycle().skip(1))
+        .collect::<Vec<(DVec2, DVec2)>>();
+
+    edges
+        .iter()
+        .copied()
+        .zip(edges.iter().copied().cycle().skip(1))
+        .map(|((start, vertex), (_, end))| {
+            let incoming_normal = (vertex - start).normalize().perp() * orientation;
+            let outgoing_normal = (end - vertex).normalize().perp() * orientation;
+
+            vertex
+                + (incoming_normal + outgoing_normal) * distance
+                    / (1.0 + incoming_normal.dot(outgoing_normal))
+        })
+        .collect::<Vec<DVec2>>()
+}
+
+fn get_reversed(mut points: Vec<DVec2>) -> Vec<DVec2> {
+    points.reverse();
+    points
+}
+
+fn get_circle_points(center: DVec2, radius: f64, tolerance: f64) -> Vec<DVec2> {
+    let segments = get_circle_segments(radius, tolerance);
+
+    (0..segments)
+        .map(|i| center + radius * DVec2::from_angle(TAU * i as f64 / segments as f64))
+        .collect::<Vec<DVec2>>()
+}
+
+/// Returns contours covering a stroke of the given thickness centered on the
+/// closed outline through `points`. The contours must be filled with
+/// [`FillRule::NonZero`]. Round joins are flattened to within `tolerance`.
+pub(crate) fn stroke_closed(
+    points: &[DVec2],
+    thickness: f64,
+    join: LineJoin,
+    tolerance: f64,
+) -> Vec<Vec<DVec2>> {
+    let mut points = points.to_vec();
+    points.dedup();
+
+    if points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+
+    if points.len() < 2 {
+        return Vec::new();
+    }
+
+    let segments = points
+        .iter()
+        .copied()
+        .zip(points.iter().copied().cycle().skip(1))
+        .collect::<Vec<(DVec2, DVec2)>>();
+
+    let offset = thickness / 2.0;
+
+    let mut contours = segments
+        .iter()
+        .map(|(start, end)| get_segment_quad(*start, *end, offset, 0.0, 0.0))
+        .collect::<Vec<Vec<DVec2>>>();
+
+    contours.extend(
+        segments
+            .iter()
+            .copied()
+            .zip(segments.iter().copied().cycle().skip(1))
+            .filter_map(|((start, vertex), (_, end))| {
+                get_join(start, vertex, end, offset, join, tolerance)
+            }),
+    );
+
+    contours
+}
+
+/// Returns contours covering a stroke of the given thickness centered on the
+/// open polyline through `points`. The contours must be filled with
+/// [`FillRule::NonZero`]. Round joins and caps are flattened to within
+/// `tolerance`.
+pub(crate) fn stroke_open(
+    points: &[DVec2],
+    thickness: f64,
+    join: LineJoin,
+    cap: LineCap,
+    tolerance: f64,
+) -> Vec<Vec<DVec2>> {
+    let mut points = points.to_vec();
+    points.dedup();
+
+    let offset = thickness / 2.0;
+
+    let (Some(first), Some(last)) = (points.first().copied(), points.last().copied()) else {
+        return Vec::new();
+    };
+
+    if points.len() == 1 {
+        return match cap {
+            LineCap::Butt => Vec::new(),
+            LineCap::Round => vec![get_circle_points(first, offset, tolerance)],
+            LineCap::Square => vec![get_oriented(vec![
+                first + dvec2(-offset, -offset),
+                first + dvec2(offset, -offset),
+                first + dvec2(offset, offset),
+                first + dvec2(-offset, offset),
+            ])],
+        };
+    }
+
+    let cap_extension = match cap {
+        LineCap::Square => offset,
+        LineCap::Butt | LineCap::Round => 0.0,
+    };
+
+    let segment_count = points.len() - 1;
+
+    let mut contours = points
+        .iter()
+        .copied()
+        .tuple_windows()
+        .enumerate()
+        .map(|(i, (start, end))| {
+            let start_extension = if i == 0 { cap_extension } else { 0.0 };
+            let end_extension = if i == segment_count - 1 {
+                cap_extension
+            } else {
+                0.0
+            };
+
+            get_segment_quad(start, end, offset, start_extension, end_extension)
+        })
+        .collect::<Vec<Vec<DVec2>>>();
+
+    contours.extend(
+        points
+            .iter()
+            .copied()
+            .tuple_windows()
+            .filter_map(|(start, vertex, end)| {
+                get_join(start, vertex, end, offset, join, tolerance)
+            }),
+    );
+
+    if cap == LineCap::Round {
+        contours.push(get_circle_points(first, offset, tolerance));
+        contours.push(get_circle_points(last, offset, tolerance));
+    }
+
+    contours
+}
+
+/// Returns contours covering a stroke of the given thickness centered on the
+/// outline through `points`, split into dashes unless `stroke_style` is
+/// solid. The contours must be filled with [`FillRule::NonZero`].
+pub(crate) fn stroke_with_style(
+    points: &[DVec2],
+    closed: bool,
+    thickness: f64,
+    join: LineJoin,
+    cap: LineCap,
+    stroke_style: &StrokeStyle,
+    tolerance: f64,
+) -> Vec<Vec<DVec2>> {
+    match (stroke_style.is_solid(), closed) {
+        (true, true) => stroke_closed(points, thickness, join, tolerance),
+        (true, false) => stroke_open(points, thickness, join, cap, tolerance),
+        (false, _) => stroke_dashed(points, closed, thickness, join, stroke_style, tolerance),
+    }
+}
+
+/// Returns contours covering the dashes of `stroke_style` along the outline
+/// through `points`, each stroked with the dash cap. The contours must be
+/// filled with [`FillRule::NonZero`].
+pub(crate) fn stroke_dashed(
+    points: &[DVec2],
+    closed: bool,
+    thickness: f64,
+    join: LineJoin,
+    stroke_style: &StrokeStyle,
+    tolerance: f64,
+) -> Vec<Vec<DVec2>> {
+    get_dashes(points, closed, stroke_style)
+        .iter()
+        .flat_map(|dash| stroke_open(dash, thickness, join, stroke_style.dash_cap, tolerance))
+        .collect::<Vec<Vec<DVec2>>>()
+}
+
+/// Splits the outline through `points` into the pieces covered by the dashes
+/// of `stroke_style`, which must not be solid. Zero length dashes are single
+/// points.
+fn get_dashes(points: &[DVec2], closed: bool, stroke_style: &StrokeStyle) -> Vec<Vec<DVec2>> {
+    let Some(first) = points.first().copied() else {
+        return Vec::new();
+    };
+
+    let pattern = if stroke_style.dashes.len().is_multiple_of(2) {
+        stroke_style.dashes.clone()
+    } else {
+        stroke_style.dashes.repeat(2)
+    };
+
+    let pattern_length = pattern.iter().sum::<f64>();
+
+    let mut index = 0;
+    let mut remaining = stroke_style.dash_offset.rem_euclid(pattern_length);
+
+    while remaining > 0.0 && remaining >= pattern[index] {
+        remaining -= pattern[index];
+        index = (index + 1) % pattern.len();
+    }
+
+    remaining = pattern[index] - remaining;
+
+    let starts_on = index.is_multiple_of(2);
+    let mut on = starts_on;
+    let mut dashes = Vec::new();
+    let mut dash = if on { vec![first] } else { Vec::new() };
+
+    let last = if closed { Some(first) } else { None };
+
+    for (start, end) in points.iter().copied().chain(last).tuple_windows() {
+        let length = start.distance(end);
+
+        if length == 0.0 {
+            continue;
+        }
+
+        let mut position = 0.0;
+
+        while length - position >= remaining {
+            position += remaining;
+
+            let point = start.lerp(end, position / length);
+
+            if on {
+                dash.push(point);
+                dashes.push(std::mem::take(&mut dash));
+            } else {
+                dash = vec![point];
+            }
+
+            on = !on;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        }
+
+        remaining -= length - position;
+
+        if on {
+            dash.push(end);
+        }
+    }
+
+    if on {
+        match dashes.first_mut() {
+            Some(first_dash) if closed && starts_on => {
+                dash.extend(first_dash.iter().skip(1));
+                *first_dash = dash;
+            }
+            _ => dashes.push(dash),
+        }
+    }
+
+    dashes
+}
+
+fn get_segment_quad(
+    start: DVec2,
+    end: DVec2,
+    offset: f64,
+    start_extension: f64,
+    end_extension: f64,
+) -> Vec<DVec2> {
+    let direction = (end - start).normalize();
+    let normal = direction.perp() * offset;
+    let start = start - direction * start_extension;
+    let end = end + direction * end_extension;
+
+    get_oriented(vec![
+        start + normal,
+        end + normal,
+        end - normal,
+        start - normal,
+    ])
+}
+
+fn get_join(
+    start: DVec2,
+    vertex: DVec2,
+    end: DVec2,
+    offset: f64,
+    join: LineJoin,
+    tolerance: f64,
+) -> Option<Vec<DVec2>> {
+    let incoming = (vertex - start).normalize();
+    let outgoing = (end - vertex).normalize();
+    let turn = incoming.perp_dot(outgoing);
+
+    if turn.abs() < f64::EPSILON {
+        return None;
+    }
+
+    let side = -turn.signum();
+    let incoming_normal = incoming.perp() * offset * side;
+    let outgoing_normal = outgoing.perp() * offset * side;
+
+    let bevel = vec![vertex, vertex + incoming_normal, vertex + outgoing_normal];
+
+    let join_points = match join {
+        LineJoin::Miter(limit) => {
+            let miter_direction = (incoming_normal + outgoing_normal).normalize();
+            let miter_ratio = 1.0 / miter_direction.dot(incoming_normal / offset);
+
+            if miter_ratio <= limit {
+                vec![
+                    vertex,
+                    vertex + incoming_normal,
+                    vertex + miter_direction * offset * miter_ratio,
+                    vertex + outgoing_normal,
+                ]
+            } else {
+                bevel
+            }
+        }
+        LineJoin::Round => get_circle_points(vertex, offset, tolerance),
+        LineJoin::Bevel => bevel,
+    };
+
+    Some(get_oriented(join_points))
+}
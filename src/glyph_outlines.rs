@@ -0,0 +1,55 @@
+use ab_glyph::{Font, GlyphId, OutlineCurve};
+use glam::{DVec2, dvec2};
+
+use crate::Path;
+
+/// Adds the outline of the glyph `id` of `font` to `path` as closed
+/// subpaths, with the origin of the glyph on the baseline at `origin` and
+/// `scale` converting font units to the coordinates of the path.
+pub(crate) fn push_glyph_outline(
+    path: &mut Path,
+    font: &impl Font,
+    id: GlyphId,
+    origin: DVec2,
+    scale: f64,
+) {
+    let Some(outline) = font.outline(id) else {
+        return;
+    };
+
+    let glyph_scale = dvec2(scale, -scale);
+    let map_point =
+        |point: ab_glyph::Point| origin + dvec2(point.x as f64, point.y as f64) * glyph_scale;
+
+    let mut current = None;
+
+    for curve in outline.curves {
+        let (start, end) = match curve {
+            OutlineCurve::Line(start, end)
+            | OutlineCurve::Quad(start, _, end)
+            | OutlineCurve::Cubic(start, _, _, end) => (map_point(start), map_point(end)),
+        };
+
+        if current != Some(start) {
+            if current.is_some() {
+                path.close();
+            }
+
+            path.move_to(start);
+        }
+
+        match curve {
+            OutlineCurve::Line(_, _) => path.line_to(end),
+            OutlineCurve::Quad(_, control, _) => path.quad_to(map_point(control), end),
+            OutlineCurve::Cubic(_, control_1, control_2, _) => {
+                path.cubic_to(map_point(control_1), map_point(control_2), end)
+            }
+        };
+
+        current = Some(end);
+    }
+
+    if current.is_some() {
+        path.close();
+    }
+}
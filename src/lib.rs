@@ -1,9 +1,30 @@
 use anchor2d::Anchor2D;
-use glam::DVec2;
+use glam::{DAffine2, DVec2};
 use palette::Srgba;
 
+pub mod blend_mode;
+pub mod corner_radii;
+#[cfg(any(feature = "image", feature = "macroquad"))]
+mod font_fallback;
+mod font_metrics;
+pub mod font_registries;
+mod geometry;
+#[cfg(any(feature = "image", feature = "macroquad"))]
+mod glyph_outlines;
 pub mod image_registries;
+pub mod mesh;
+pub mod paint;
+pub mod path;
 pub mod renderers;
+pub mod rich_text;
+#[cfg(feature = "image")]
+mod shaping;
+pub mod stroke;
+mod text_decoration;
+pub mod text_layout;
+pub mod text_metrics;
+pub mod text_path;
+pub mod text_style;
 
 #[cfg(feature = "image")]
 pub use renderers::image::ImageRenderer;
@@ -11,10 +32,100 @@ pub use renderers::image::ImageRenderer;
 #[cfg(feature = "macroquad")]
 pub use renderers::macroquad::MacroquadRenderer;
 
+pub use blend_mode::BlendMode;
+pub use corner_radii::CornerRadii;
+pub use mesh::MeshVertex;
+pub use paint::{ColorSpace, ColorStop, Gradient, GradientKind, Paint, SpreadMode};
+pub use path::{FillRule, Path, PathCommand};
+pub use rich_text::TextSpan;
+pub use stroke::{LineCap, LineJoin, StrokeStyle};
+pub use text_layout::{TextAlign, TextBox, TextOverflow};
+pub use text_metrics::TextMetrics;
+pub use text_path::TextPath;
+pub use text_style::{TextAnchoring, TextBackground, TextStyle};
+
 pub trait Renderer {
+    fn get_blend_mode(&self) -> BlendMode;
+
+    /// Sets how everything drawn afterwards is combined with what is already
+    /// drawn.
+    fn set_blend_mode(&mut self, blend_mode: BlendMode);
+
+    fn get_stroke_style(&self) -> &StrokeStyle;
+
+    /// Sets the dash pattern every following outline is drawn with.
+    fn set_stroke_style(&mut self, stroke_style: StrokeStyle);
+
+    /// Returns the transform from the current local coordinates to the
+    /// coordinates of the renderer.
+    fn get_transform(&self) -> DAffine2;
+
+    /// Returns how far, in the current local coordinates, flattened curves
+    /// may stray from the true curves without it showing once drawn.
+    fn get_flattening_tolerance(&self) -> f64 {
+        geometry::get_transformed_tolerance(self.get_transform())
+    }
+
+    /// Applies `transform` to everything drawn until the matching
+    /// [`Renderer::pop_transform`], inside of the transforms already pushed.
+    fn push_transform(&mut self, transform: DAffine2);
+
+    fn pop_transform(&mut self);
+
+    fn push_translation(&mut self, translation: DVec2) {
+        self.push_transform(DAffine2::from_translation(translation));
+    }
+
+    fn push_rotation(&mut self, rotation: f64) {
+        self.push_transform(DAffine2::from_angle(rotation));
+    }
+
+    fn push_scale(&mut self, scale: DVec2) {
+        self.push_transform(DAffine2::from_scale(scale));
+    }
+
+    /// Restricts drawing to the rectangle until the matching
+    /// [`Renderer::pop_clip`], inside of the clips already pushed.
+    fn push_clip_rect(
+        &mut self,
+        position: DVec2,
+        width: f64,
+        height: f64,
+        offset: DVec2,
+        rotation: f64,
+    );
+
+    /// Restricts drawing to the polygon through `points` until the matching
+    /// [`Renderer::pop_clip`], inside of the clips already pushed.
+    fn push_clip_polygon(&mut self, points: &[DVec2]);
+
+    /// Restricts drawing to the inside of `path` until the matching
+    /// [`Renderer::pop_clip`], inside of the clips already pushed.
+    fn push_clip_path(&mut self, path: &Path, fill_rule: FillRule);
+
+    fn pop_clip(&mut self);
+
+    /// Draws everything until the matching [`Renderer::end_layer`] into an
+    /// empty layer, which is then drawn as one image with `opacity` and
+    /// `blend_mode`.
+    fn begin_layer(&mut self, opacity: f32, blend_mode: BlendMode);
+
+    fn end_layer(&mut self);
+
     fn render_point(&mut self, position: DVec2, color: Srgba);
     fn render_line(&mut self, start: DVec2, end: DVec2, thickness: f64, color: Srgba);
-    fn render_circle(&mut self, position: DVec2, radius: f64, color: Srgba);
+
+    /// Strokes the open polyline through `points` as one continuous line.
+    fn render_polyline(
+        &mut self,
+        points: &[DVec2],
+        thickness: f64,
+        join: LineJoin,
+        cap: LineCap,
+        color: Srgba,
+    );
+
+    fn render_circle(&mut self, position: DVec2, radius: f64, paint: Paint);
     fn render_circle_lines(&mut self, position: DVec2, radius: f64, thickness: f64, color: Srgba);
 
     fn render_arc(
@@ -24,9 +135,13 @@ pub trait Renderer {
         rotation: f64,
         sides: u8,
         arc: f64,
-        color: Srgba,
+        paint: Paint,
     );
 
+    /// Strokes the circular arc from `rotation` through `arc`, with as many
+    /// segments as the arc covers of a full circle split into `sides`
+    /// segments.
+    #[allow(clippy::too_many_arguments)]
     fn render_arc_lines(
         &mut self,
         position: DVec2,
@@ -38,26 +153,194 @@ pub trait Renderer {
         color: Srgba,
     );
 
+    fn render_ellipse(&mut self, position: DVec2, radii: DVec2, rotation: f64, paint: Paint);
+
+    fn render_ellipse_lines(
+        &mut self,
+        position: DVec2,
+        radii: DVec2,
+        rotation: f64,
+        thickness: f64,
+        color: Srgba,
+    );
+
+    /// Fills the slice of the ellipse from its own angle `start_angle`
+    /// through `arc`, with the ellipse rotated by `rotation`.
+    fn render_elliptical_arc(
+        &mut self,
+        position: DVec2,
+        radii: DVec2,
+        rotation: f64,
+        start_angle: f64,
+        arc: f64,
+        paint: Paint,
+    );
+
+    /// Strokes the curve of the ellipse from its own angle `start_angle`
+    /// through `arc`, with the ellipse rotated by `rotation`.
+    #[allow(clippy::too_many_arguments)]
+    fn render_elliptical_arc_lines(
+        &mut self,
+        position: DVec2,
+        radii: DVec2,
+        rotation: f64,
+        start_angle: f64,
+        arc: f64,
+        thickness: f64,
+        color: Srgba,
+    );
+
+    /// Measures `text` as [`Renderer::render_text`] would lay it out at
+    /// `size` and `style`, with one line per `\n`.
+    fn measure_text(&self, text: &str, size: f64, style: &TextStyle) -> TextMetrics;
+
     fn render_text(
         &mut self,
         text: &str,
         position: DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         color: Srgba,
     );
 
+    /// Draws `text` over an outline reaching `outline_thickness` past the
+    /// edges of its glyphs, with round corners.
+    #[allow(clippy::too_many_arguments)]
     fn render_text_outline(
         &mut self,
         text: &str,
         position: DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         outline_thickness: f64,
         color: Srgba,
         outline_color: Srgba,
     );
 
+    /// Wraps `text` into `text_box` and draws it with the box anchored at
+    /// `position`.
+    #[allow(clippy::too_many_arguments)]
+    fn render_text_box(
+        &mut self,
+        text: &str,
+        position: DVec2,
+        anchor: Anchor2D,
+        size: f64,
+        style: &TextStyle,
+        text_box: &TextBox,
+        color: Srgba,
+    ) {
+        if let Some(transform) = style.get_anchored_transform(position) {
+            self.push_transform(transform);
+            self.render_text_box(
+                text,
+                position,
+                anchor,
+                size,
+                &style.untransformed(),
+                text_box,
+                color,
+            );
+            self.pop_transform();
+
+            return;
+        }
+
+        let layout = text_box.layout(text, position, anchor, size, |line| {
+            self.measure_text(line, size, style).width
+        });
+
+        let style = TextStyle {
+            anchoring: TextAnchoring::Font,
+            ..style.clone()
+        };
+
+        let is_clipped = text_box.overflow == TextOverflow::Clip;
+
+        if is_clipped {
+            self.push_clip_rect(
+                layout.origin,
+                text_box.width,
+                layout.height,
+                DVec2::ZERO,
+                0.0,
+            );
+        }
+
+        for (run, run_position) in layout.runs {
+            self.render_text(&run, run_position, anchor2d::LGT, size, &style, color);
+        }
+
+        if is_clipped {
+            self.pop_clip();
+        }
+    }
+
+    /// Draws `text` along `path` as laid out by `text_path`, with the baseline
+    /// of each character on the path. The transform of `style` applies to each
+    /// character around the middle of its baseline.
+    fn render_text_on_path(
+        &mut self,
+        text: &str,
+        path: &Path,
+        size: f64,
+        style: &TextStyle,
+        text_path: &TextPath,
+        color: Srgba,
+    ) {
+        let tolerance = self.get_flattening_tolerance();
+        let characters = text_path.layout(text, path, tolerance, |text| {
+            self.measure_text(text, size, style).width
+        });
+
+        let style = TextStyle {
+            anchoring: TextAnchoring::Baseline,
+            ..style.clone()
+        };
+
+        for (character, position, angle) in characters {
+            self.push_transform(DAffine2::from_angle_translation(angle, position));
+            self.render_text(&character, DVec2::ZERO, anchor2d::CGB, size, &style, color);
+            self.pop_transform();
+        }
+    }
+
+    /// Returns the size of `spans` drawn one after another on one line.
+    fn measure_rich_text(&self, spans: &[TextSpan]) -> TextMetrics {
+        rich_text::measure_spans(spans, |span| {
+            self.measure_text(&span.text, span.size, &span.style)
+        })
+    }
+
+    /// Draws `spans` one after another on a shared baseline, with the line
+    /// anchored as a whole at `position` by the anchoring of the first span.
+    /// The transform of the style of each span applies around where the span
+    /// starts, so a line is turned as a whole with a pushed transform.
+    fn render_rich_text(&mut self, spans: &[TextSpan], position: DVec2, anchor: Anchor2D) {
+        let runs = rich_text::layout_spans(spans, position, anchor, |span| {
+            self.measure_text(&span.text, span.size, &span.style)
+        });
+
+        for (span, start) in spans.iter().zip(runs) {
+            let style = TextStyle {
+                anchoring: TextAnchoring::Baseline,
+                underline: span.underline || span.style.underline,
+                ..span.style.clone()
+            };
+
+            self.render_text(
+                &span.text,
+                start,
+                anchor2d::LGT,
+                span.size,
+                &style,
+                span.color,
+            );
+        }
+    }
+
     fn render_rectangle(
         &mut self,
         position: DVec2,
@@ -65,9 +348,10 @@ pub trait Renderer {
         height: f64,
         offset: DVec2,
         rotation: f64,
-        color: Srgba,
+        paint: Paint,
     );
 
+    #[allow(clippy::too_many_arguments)]
     fn render_rectangle_lines(
         &mut self,
         position: DVec2,
@@ -79,12 +363,39 @@ pub trait Renderer {
         color: Srgba,
     );
 
+    #[allow(clippy::too_many_arguments)]
+    fn render_rounded_rectangle(
+        &mut self,
+        position: DVec2,
+        width: f64,
+        height: f64,
+        offset: DVec2,
+        rotation: f64,
+        radii: CornerRadii,
+        paint: Paint,
+    );
+
+    /// Strokes the inside edge of a rounded rectangle, like
+    /// [`Renderer::render_rectangle_lines`].
+    #[allow(clippy::too_many_arguments)]
+    fn render_rounded_rectangle_lines(
+        &mut self,
+        position: DVec2,
+        width: f64,
+        height: f64,
+        offset: DVec2,
+        rotation: f64,
+        radii: CornerRadii,
+        thickness: f64,
+        color: Srgba,
+    );
+
     fn render_equilateral_triangle(
         &mut self,
         position: DVec2,
         radius: f64,
         rotation: f64,
-        color: Srgba,
+        paint: Paint,
     );
 
     fn render_equilateral_triangle_lines(
@@ -96,6 +407,78 @@ pub trait Renderer {
         color: Srgba,
     );
 
+    /// Fills the polygon through `points`, which may be convex or concave.
+    /// Each entry of `holes` is another polygon cut out of the fill.
+    fn render_polygon(&mut self, points: &[DVec2], holes: &[Vec<DVec2>], paint: Paint);
+
+    /// Strokes the outline of the polygon through `points` and of each of its
+    /// `holes`, centered on the outline.
+    fn render_polygon_lines(
+        &mut self,
+        points: &[DVec2],
+        holes: &[Vec<DVec2>],
+        thickness: f64,
+        color: Srgba,
+    );
+
+    fn render_path_fill(&mut self, path: &Path, fill_rule: FillRule, paint: Paint);
+
+    /// Strokes every subpath of `path`, joining the ends of closed subpaths
+    /// and capping the ends of open ones.
+    fn render_path_stroke(
+        &mut self,
+        path: &Path,
+        thickness: f64,
+        join: LineJoin,
+        cap: LineCap,
+        color: Srgba,
+    );
+
+    fn render_regular_polygon(
+        &mut self,
+        position: DVec2,
+        radius: f64,
+        rotation: f64,
+        sides: u8,
+        paint: Paint,
+    );
+
+    /// Strokes the inside edge of a regular polygon.
+    fn render_regular_polygon_lines(
+        &mut self,
+        position: DVec2,
+        radius: f64,
+        rotation: f64,
+        sides: u8,
+        thickness: f64,
+        color: Srgba,
+    );
+
+    /// Fills a star with `points` outer vertices at `outer_radius` and inner
+    /// vertices at `inner_radius`, the first outer vertex at angle `rotation`.
+    fn render_star(
+        &mut self,
+        position: DVec2,
+        outer_radius: f64,
+        inner_radius: f64,
+        rotation: f64,
+        points: u8,
+        paint: Paint,
+    );
+
+    /// Strokes the inside edge of a star.
+    #[allow(clippy::too_many_arguments)]
+    fn render_star_lines(
+        &mut self,
+        position: DVec2,
+        outer_radius: f64,
+        inner_radius: f64,
+        rotation: f64,
+        points: u8,
+        thickness: f64,
+        color: Srgba,
+    );
+
     fn render_image(
         &mut self,
         image_name: &str,
@@ -105,6 +488,10 @@ pub trait Renderer {
         offset: DVec2,
         rotation: f64,
     );
+
+    /// Draws the triangles formed by each three consecutive `indices` into
+    /// `vertices`, sampling the registered image named `image_name` if given.
+    fn render_mesh(&mut self, vertices: &[MeshVertex], indices: &[u32], image_name: Option<&str>);
 }
 
 pub trait ImageRegistry {
@@ -0,0 +1,24 @@
+use glam::DVec2;
+use palette::Srgba;
+
+/// A vertex of a triangle mesh drawn with [`crate::Renderer::render_mesh`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MeshVertex {
+    pub position: DVec2,
+    /// The texture coordinate, from `(0, 0)` at the top left of the image to
+    /// `(1, 1)` at the bottom right.
+    pub uv: DVec2,
+    /// Multiplied with the sampled image color, or used as is without an
+    /// image.
+    pub color: Srgba,
+}
+
+impl MeshVertex {
+    pub fn new(position: DVec2, uv: DVec2, color: Srgba) -> Self {
+        Self {
+            position,
+            uv,
+            color,
+        }
+    }
+}
@@ -0,0 +1,283 @@
+#[cfg(feature = "image")]
+use std::f64::consts::TAU;
+
+use glam::DVec2;
+use palette::{FromColor, Oklab, Srgba};
+#[cfg(feature = "image")]
+use palette::{LinSrgb, Srgb, WithAlpha};
+
+/// What fills a shape: a flat color or a gradient.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Srgba),
+    Gradient(Gradient),
+}
+
+impl From<Srgba> for Paint {
+    fn from(color: Srgba) -> Self {
+        Self::Solid(color)
+    }
+}
+
+impl From<Gradient> for Paint {
+    fn from(gradient: Gradient) -> Self {
+        Self::Gradient(gradient)
+    }
+}
+
+/// The geometry of a gradient, in the same coordinates as the shapes it
+/// fills.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    /// Runs along the line from `start` at offset 0 to `end` at offset 1.
+    Linear { start: DVec2, end: DVec2 },
+    /// Runs outwards from `center` at offset 0 to `radius` at offset 1.
+    Radial { center: DVec2, radius: f64 },
+    /// Runs around `center`, starting at `start_angle` at offset 0 and going
+    /// a full turn in the direction of increasing angles to offset 1.
+    Conic { center: DVec2, start_angle: f64 },
+}
+
+/// How a gradient continues outside of the offsets 0 to 1.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SpreadMode {
+    /// Extends the colors of the first and last stops.
+    #[default]
+    Pad,
+    /// Starts over from the first stop.
+    Repeat,
+    /// Runs back and forth through the stops.
+    Reflect,
+}
+
+/// The color space gradient stops are interpolated in.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    #[default]
+    Srgb,
+    LinearSrgb,
+    Oklab,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    pub offset: f64,
+    pub color: Srgba,
+}
+
+impl ColorStop {
+    pub fn new(offset: f64, color: Srgba) -> Self {
+        Self { offset, color }
+    }
+}
+
+/// Colors blended between stops along the geometry of `kind`.
+///
+/// The macroquad renderer draws at most 16 stops, dropping the inner stops
+/// that change the gradient the least from gradients with more.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub stops: Vec<ColorStop>,
+    pub spread: SpreadMode,
+    pub color_space: ColorSpace,
+}
+
+impl Gradient {
+    pub fn new(
+        kind: GradientKind,
+        stops: Vec<ColorStop>,
+        spread: SpreadMode,
+        color_space: ColorSpace,
+    ) -> Self {
+        Self {
+            kind,
+            stops,
+            spread,
+            color_space,
+        }
+    }
+
+    pub fn linear(start: DVec2, end: DVec2, stops: Vec<ColorStop>) -> Self {
+        Self::new(
+            GradientKind::Linear { start, end },
+            stops,
+            SpreadMode::default(),
+            ColorSpace::default(),
+        )
+    }
+
+    pub fn radial(center: DVec2, radius: f64, stops: Vec<ColorStop>) -> Self {
+        Self::new(
+            GradientKind::Radial { center, radius },
+            stops,
+            SpreadMode::default(),
+            ColorSpace::default(),
+        )
+    }
+
+    pub fn conic(center: DVec2, start_angle: f64, stops: Vec<ColorStop>) -> Self {
+        Self::new(
+            GradientKind::Conic {
+                center,
+                start_angle,
+            },
+            stops,
+            SpreadMode::default(),
+            ColorSpace::default(),
+        )
+    }
+
+    /// Returns the stops sorted by offset, with their colors converted to
+    /// premultiplied components in the interpolation color space.
+    pub(crate) fn get_interpolation_stops(&self) -> Vec<(f32, [f32; 4])> {
+        let mut stops = self
+            .stops
+            .iter()
+            .map(|stop| {
+                (
+                    stop.offset as f32,
+                    self.color_space.get_components(stop.color),
+                )
+            })
+            .collect::<Vec<(f32, [f32; 4])>>();
+
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        stops
+    }
+
+    /// Returns the offset of `point` along the gradient, before spreading.
+    #[cfg(feature = "image")]
+    fn get_offset(&self, point: DVec2) -> f64 {
+        match self.kind {
+            GradientKind::Linear { start, end } => {
+                let direction = end - start;
+                let length_squared = direction.length_squared();
+
+                if length_squared == 0.0 {
+                    0.0
+                } else {
+                    (point - start).dot(direction) / length_squared
+                }
+            }
+            GradientKind::Radial { center, radius } => {
+                if radius == 0.0 {
+                    1.0
+                } else {
+                    (point - center).length() / radius
+                }
+            }
+            GradientKind::Conic {
+                center,
+                start_angle,
+            } => ((point - center).to_angle() - start_angle).rem_euclid(TAU) / TAU,
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl SpreadMode {
+    fn apply(&self, offset: f64) -> f64 {
+        match self {
+            Self::Pad => offset.clamp(0.0, 1.0),
+            Self::Repeat => offset.rem_euclid(1.0),
+            Self::Reflect => 1.0 - (offset.rem_euclid(2.0) - 1.0).abs(),
+        }
+    }
+}
+
+impl ColorSpace {
+    /// Converts a color to premultiplied components in this color space.
+    pub(crate) fn get_components(&self, color: Srgba) -> [f32; 4] {
+        let [first, second, third] = match self {
+            Self::Srgb => [color.red, color.green, color.blue],
+            Self::LinearSrgb => {
+                let linear = color.color.into_linear::<f32>();
+                [linear.red, linear.green, linear.blue]
+            }
+            Self::Oklab => {
+                let oklab = Oklab::from_color(color.color.into_linear::<f32>());
+                [oklab.l, oklab.a, oklab.b]
+            }
+        };
+
+        [
+            first * color.alpha,
+            second * color.alpha,
+            third * color.alpha,
+            color.alpha,
+        ]
+    }
+
+    /// Converts premultiplied components in this color space back to a color.
+    #[cfg(feature = "image")]
+    pub(crate) fn get_color(&self, components: [f32; 4]) -> Srgba {
+        let [first, second, third, alpha] = components;
+
+        if alpha <= 0.0 {
+            return Srgba::new(0.0, 0.0, 0.0, 0.0);
+        }
+
+        let [first, second, third] = [first / alpha, second / alpha, third / alpha];
+
+        let color = match self {
+            Self::Srgb => Srgb::new(first, second, third),
+            Self::LinearSrgb => Srgb::from_linear(LinSrgb::new(first, second, third)),
+            Self::Oklab => Srgb::from_linear(LinSrgb::from_color(Oklab::new(first, second, third))),
+        };
+
+        color.with_alpha(alpha)
+    }
+}
+
+/// Evaluates a paint at many points, converting the gradient stops once.
+#[cfg(feature = "image")]
+pub(crate) enum PaintSampler<'a> {
+    Solid(Srgba),
+    Gradient {
+        gradient: &'a Gradient,
+        stops: Vec<(f32, [f32; 4])>,
+    },
+}
+
+#[cfg(feature = "image")]
+impl<'a> PaintSampler<'a> {
+    pub fn new(paint: &'a Paint) -> Self {
+        match paint {
+            Paint::Solid(color) => Self::Solid(*color),
+            Paint::Gradient(gradient) => Self::Gradient {
+                gradient,
+                stops: gradient.get_interpolation_stops(),
+            },
+        }
+    }
+
+    pub fn sample(&self, point: DVec2) -> Srgba {
+        match self {
+            Self::Solid(color) => *color,
+            Self::Gradient { gradient, stops } => {
+                let offset = gradient.spread.apply(gradient.get_offset(point)) as f32;
+
+                let components = match stops
+                    .iter()
+                    .position(|(stop_offset, _)| *stop_offset > offset)
+                {
+                    None => stops.last().map(|(_, components)| *components),
+                    Some(0) => stops.first().map(|(_, components)| *components),
+                    Some(i) => {
+                        let (start_offset, start) = stops[i - 1];
+                        let (end_offset, end) = stops[i];
+                        let t = (offset - start_offset) / (end_offset - start_offset);
+
+                        Some([0, 1, 2, 3].map(|j| start[j] + (end[j] - start[j]) * t))
+                    }
+                };
+
+                components
+                    .map(|components| gradient.color_space.get_color(components))
+                    .unwrap_or(Srgba::new(0.0, 0.0, 0.0, 0.0))
+            }
+        }
+    }
+}
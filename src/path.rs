@@ -0,0 +1,328 @@
+use std::f64::consts::{PI, TAU};
+
+use glam::{DVec2, dvec2};
+
+use crate::geometry::get_circle_segments;
+
+/// Decides which areas enclosed by a path are inside it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FillRule {
+    /// Inside if the outlines wind around the area a non-zero number of times.
+    #[default]
+    NonZero,
+    /// Inside if a ray from the area crosses the outlines an odd number of times.
+    EvenOdd,
+}
+
+impl FillRule {
+    pub(crate) fn is_inside(&self, winding: i32) -> bool {
+        match self {
+            Self::NonZero => winding != 0,
+            Self::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathCommand {
+    MoveTo(DVec2),
+    LineTo(DVec2),
+    QuadTo(DVec2, DVec2),
+    CubicTo(DVec2, DVec2, DVec2),
+    ArcTo {
+        radii: DVec2,
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        end: DVec2,
+    },
+    Close,
+}
+
+/// A sequence of subpaths made of lines and curves.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Path {
+    commands: Vec<PathCommand>,
+}
+
+/// A subpath flattened into points, ready to be filled or stroked.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FlattenedSubpath {
+    pub points: Vec<DVec2>,
+    pub closed: bool,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A path through `points` with straight lines.
+    pub fn polyline(points: &[DVec2]) -> Self {
+        let mut path = Self::new();
+
+        for (i, point) in points.iter().copied().enumerate() {
+            if i == 0 {
+                path.move_to(point);
+            } else {
+                path.line_to(point);
+            }
+        }
+
+        path
+    }
+
+    /// A path along the circle around `position`, from the angle `rotation`
+    /// through `arc`, like the arcs of [`crate::Renderer::render_arc`].
+    pub fn circular_arc(position: DVec2, radius: f64, rotation: f64, arc: f64) -> Self {
+        let mut path = Self::new();
+
+        path.move_to(position + radius * DVec2::from_angle(rotation));
+
+        // Arcs of up to half a turn keep the SVG arc to each end unambiguous.
+        let pieces = (arc.abs() / PI).ceil().max(1.0) as usize;
+
+        for i in 1..=pieces {
+            let angle = rotation + arc * i as f64 / pieces as f64;
+
+            path.arc_to(
+                DVec2::splat(radius),
+                0.0,
+                false,
+                arc > 0.0,
+                position + radius * DVec2::from_angle(angle),
+            );
+        }
+
+        path
+    }
+
+    pub fn get_commands(&self) -> &[PathCommand] {
+        &self.commands
+    }
+
+    /// Starts a new subpath at `point`.
+    pub fn move_to(&mut self, point: DVec2) -> &mut Self {
+        self.commands.push(PathCommand::MoveTo(point));
+        self
+    }
+
+    pub fn line_to(&mut self, point: DVec2) -> &mut Self {
+        self.commands.push(PathCommand::LineTo(point));
+        self
+    }
+
+    /// Adds a quadratic Bézier curve to `end`.
+    pub fn quad_to(&mut self, control: DVec2, end: DVec2) -> &mut Self {
+        self.commands.push(PathCommand::QuadTo(control, end));
+        self
+    }
+
+    /// Adds a cubic Bézier curve to `end`.
+    pub fn cubic_to(&mut self, control_1: DVec2, control_2: DVec2, end: DVec2) -> &mut Self {
+        self.commands
+            .push(PathCommand::CubicTo(control_1, control_2, end));
+        self
+    }
+
+    /// Adds an elliptical arc to `end`, following the SVG arc conventions: the
+    /// ellipse has the given `radii` rotated by `rotation`, `large_arc` picks
+    /// the longer of the two possible arcs and `sweep` picks the one drawn in
+    /// the direction of increasing angles.
+    pub fn arc_to(
+        &mut self,
+        radii: DVec2,
+        rotation: f64,
+        large_arc: bool,
+        sweep: bool,
+        end: DVec2,
+    ) -> &mut Self {
+        self.commands.push(PathCommand::ArcTo {
+            radii,
+            rotation,
+            large_arc,
+            sweep,
+            end,
+        });
+        self
+    }
+
+    /// Closes the current subpath with a line back to its start.
+    pub fn close(&mut self) -> &mut Self {
+        self.commands.push(PathCommand::Close);
+        self
+    }
+
+    /// Flattens the curves of the path into lines that stay within
+    /// `tolerance` of the true curves.
+    pub(crate) fn flatten(&self, tolerance: f64) -> Vec<FlattenedSubpath> {
+        let mut subpaths = Vec::new();
+        let mut points: Vec<DVec2> = Vec::new();
+        let mut current = DVec2::ZERO;
+        let mut start = DVec2::ZERO;
+
+        for command in &self.commands {
+            if points.is_empty() && !matches!(command, PathCommand::MoveTo(_)) {
+                points.push(current);
+            }
+
+            match *command {
+                PathCommand::MoveTo(point) => {
+                    if points.len() > 1 {
+                        subpaths.push(FlattenedSubpath {
+                            points: std::mem::take(&mut points),
+                            closed: false,
+                        });
+                    }
+
+                    points = vec![point];
+                    start = point;
+                    current = point;
+                }
+                PathCommand::LineTo(point) => {
+                    points.push(point);
+                    current = point;
+                }
+                PathCommand::QuadTo(control, end) => {
+                    let deviation = (current - 2.0 * control + end).length();
+                    let segments = get_curve_segments(deviation / (4.0 * tolerance));
+
+                    points.extend((1..=segments).map(|i| {
+                        let t = i as f64 / segments as f64;
+                        let mt = 1.0 - t;
+                        current * mt * mt + control * 2.0 * mt * t + end * t * t
+                    }));
+
+                    current = end;
+                }
+                PathCommand::CubicTo(control_1, control_2, end) => {
+                    let deviation = (current - 2.0 * control_1 + control_2)
+                        .length()
+                        .max((control_1 - 2.0 * control_2 + end).length());
+                    let segments = get_curve_segments(3.0 * deviation / (4.0 * tolerance));
+
+                    points.extend((1..=segments).map(|i| {
+                        let t = i as f64 / segments as f64;
+                        let mt = 1.0 - t;
+                        current * mt * mt * mt
+                            + control_1 * 3.0 * mt * mt * t
+                            + control_2 * 3.0 * mt * t * t
+                            + end * t * t * t
+                    }));
+
+                    current = end;
+                }
+                PathCommand::ArcTo {
+                    radii,
+                    rotation,
+                    large_arc,
+                    sweep,
+                    end,
+                } => {
+                    points.extend(get_arc_points(
+                        current, radii, rotation, large_arc, sweep, end, tolerance,
+                    ));
+
+                    current = end;
+                }
+                PathCommand::Close => {
+                    if points.len() > 1 {
+                        subpaths.push(FlattenedSubpath {
+                            points: std::mem::take(&mut points),
+                            closed: true,
+                        });
+                    } else {
+                        points.clear();
+                    }
+
+                    current = start;
+                }
+            }
+        }
+
+        if points.len() > 1 {
+            subpaths.push(FlattenedSubpath {
+                points,
+                closed: false,
+            });
+        }
+
+        subpaths
+    }
+}
+
+fn get_curve_segments(squared_segments: f64) -> usize {
+    (squared_segments.sqrt().ceil() as usize).clamp(1, 1024)
+}
+
+/// Returns the points of an SVG-style arc from `start` to `end`, excluding
+/// `start`.
+fn get_arc_points(
+    start: DVec2,
+    radii: DVec2,
+    rotation: f64,
+    large_arc: bool,
+    sweep: bool,
+    end: DVec2,
+    tolerance: f64,
+) -> Vec<DVec2> {
+    let mut radii = radii.abs();
+
+    if start == end {
+        return Vec::new();
+    }
+
+    if radii.x == 0.0 || radii.y == 0.0 {
+        return vec![end];
+    }
+
+    let axis = DVec2::from_angle(rotation);
+    let inverse_axis = dvec2(axis.x, -axis.y);
+    let relative = inverse_axis.rotate((start - end) / 2.0);
+
+    let lambda = (relative.x / radii.x).powi(2) + (relative.y / radii.y).powi(2);
+
+    if lambda > 1.0 {
+        radii *= lambda.sqrt();
+    }
+
+    let numerator = (radii.x * radii.y).powi(2)
+        - (radii.x * relative.y).powi(2)
+        - (radii.y * relative.x).powi(2);
+    let denominator = (radii.x * relative.y).powi(2) + (radii.y * relative.x).powi(2);
+    let sign = if large_arc == sweep { -1.0 } else { 1.0 };
+    let coefficient = sign * (numerator / denominator).max(0.0).sqrt();
+
+    let relative_center = coefficient
+        * dvec2(
+            radii.x * relative.y / radii.y,
+            -radii.y * relative.x / radii.x,
+        );
+    let center = axis.rotate(relative_center) + (start + end) / 2.0;
+
+    let start_vector = (relative - relative_center) / radii;
+    let end_vector = (-relative - relative_center) / radii;
+
+    let start_angle = start_vector.to_angle();
+    let mut sweep_angle = (end_vector.to_angle() - start_angle).rem_euclid(TAU);
+
+    if !sweep && sweep_angle > 0.0 {
+        sweep_angle -= TAU;
+    }
+
+    let segments = ((get_circle_segments(radii.max_element(), tolerance) as f64 * sweep_angle.abs()
+        / TAU)
+        .ceil() as usize)
+        .max(1);
+
+    (1..=segments)
+        .map(|i| {
+            if i == segments {
+                return end;
+            }
+
+            let angle = start_angle + sweep_angle * i as f64 / segments as f64;
+            center + axis.rotate(radii * DVec2::from_angle(angle))
+        })
+        .collect::<Vec<DVec2>>()
+}
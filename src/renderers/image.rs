@@ -1,28 +1,36 @@
 use std::{
     borrow::Borrow,
-    f64::consts::{FRAC_PI_2, PI},
+    f64::consts::{PI, TAU},
     iter::once,
 };
 
-use ab_glyph::FontArc;
-use anchor2d::{Anchor2D, HorizontalAnchor, VerticalAnchorContext, VerticalAnchorValue};
-use glam::{DVec2, IVec2, dvec2, ivec2};
+use ab_glyph::{Font, FontArc, point};
+use anchor2d::{Anchor2D, HorizontalAnchor};
+use glam::{DAffine2, DVec2, dvec2};
 use image::{
-    Rgba, RgbaImage,
+    GrayImage, Luma, Rgba, RgbaImage,
     imageops::{FilterType, overlay, resize},
 };
-use imageproc::{
-    drawing::{
-        draw_filled_circle_mut, draw_filled_rect_mut, draw_polygon_mut, draw_text_mut, text_size,
-    },
-    geometric_transformations::{Interpolation, rotate},
-    point::Point,
-    rect::Rect,
-};
 use itertools::Itertools;
 use palette::Srgba;
 
-use crate::{Renderer, image_registries::image_image_registry::ImageImageRegistry};
+use crate::{
+    BlendMode, CornerRadii, FillRule, LineCap, LineJoin, MeshVertex, Paint, Path, Renderer,
+    StrokeStyle, TextAnchoring, TextMetrics, TextStyle,
+    font_metrics::FontMetrics,
+    font_registries::image_font_registry::ImageFontRegistry,
+    geometry::{
+        Scanner, get_arc_lines_contours, get_elliptical_arc_points, get_inset_lines_contours,
+        get_regular_polygon_points, get_rounded_rectangle_lines_contours,
+        get_rounded_rectangle_points, get_star_points, get_transformed_tolerance, stroke_closed,
+        stroke_with_style,
+    },
+    glyph_outlines::push_glyph_outline,
+    image_registries::image_image_registry::ImageImageRegistry,
+    paint::PaintSampler,
+    shaping::{ShapedLine, Shaper, get_font_scale},
+    text_decoration::{DecorationMetrics, render_text_background, render_text_decorations},
+};
 
 fn srgba_to_rgba8(color: Srgba) -> Rgba<u8> {
     let red = (color.red * 255.0).round().clamp(0.0, 255.0) as u8;
@@ -32,8 +40,95 @@ fn srgba_to_rgba8(color: Srgba) -> Rgba<u8> {
     Rgba([red, green, blue, alpha])
 }
 
+fn get_font_metrics(font: &FontArc, size: f64) -> FontMetrics {
+    FontMetrics::from_font(font, get_font_scale(font, size))
+}
+
+fn get_decoration_metrics(font: &FontArc, size: f64) -> DecorationMetrics {
+    DecorationMetrics::from_font(
+        font,
+        &get_font_metrics(font, size),
+        get_font_scale(font, size),
+    )
+}
+
+/// Returns the metrics of the first of `fonts` merged with those of every
+/// other font `text` is drawn with.
+fn get_text_font_metrics(shaper: &Shaper, text: &str, size: f64) -> FontMetrics {
+    text.split("\n")
+        .flat_map(|line| shaper.shape(line, size, 0.0).get_font_indices())
+        .unique()
+        .fold(
+            get_font_metrics(&shaper.fonts[0], size),
+            |font_metrics, font_index| {
+                font_metrics.merged(&get_font_metrics(&shaper.fonts[font_index], size))
+            },
+        )
+}
+
+fn rgba8_to_srgba(Rgba([red, green, blue, alpha]): Rgba<u8>) -> Srgba {
+    Srgba::new(red, green, blue, alpha).into_format::<f32, f32>()
+}
+
+/// The supersampled image, drawn to with a blend mode only where the
+/// innermost clip mask is set.
+struct DrawTarget<'a> {
+    image: &'a mut RgbaImage,
+    clip_mask: Option<&'a GrayImage>,
+    blend_mode: BlendMode,
+}
+
+impl<'a> DrawTarget<'a> {
+    fn new(
+        image: &'a mut RgbaImage,
+        clip_mask: Option<&'a GrayImage>,
+        blend_mode: BlendMode,
+    ) -> Self {
+        Self {
+            image,
+            clip_mask,
+            blend_mode,
+        }
+    }
+
+    fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    fn draw_pixel(&mut self, x: u32, y: u32, color: Srgba) {
+        if self
+            .clip_mask
+            .is_some_and(|clip_mask| clip_mask.get_pixel(x, y).0[0] == 0)
+        {
+            return;
+        }
+
+        let pixel = self.image.get_pixel_mut(x, y);
+
+        // Opaque colors drawn normally cover the pixel, which saves the
+        // conversions of compositing.
+        *pixel = if self.blend_mode == BlendMode::Normal && color.alpha >= 1.0 {
+            srgba_to_rgba8(color)
+        } else {
+            srgba_to_rgba8(self.blend_mode.composite(color, rgba8_to_srgba(*pixel)))
+        };
+    }
+}
+
+/// A layer being drawn into, with what it is drawn over once it ends.
 #[derive(Clone)]
-pub struct ImageRenderer<R: Borrow<ImageImageRegistry>> {
+struct Layer {
+    parent_image: RgbaImage,
+    opacity: f32,
+    blend_mode: BlendMode,
+}
+
+#[derive(Clone)]
+pub struct ImageRenderer<R: Borrow<ImageImageRegistry>, F: Borrow<ImageFontRegistry>> {
     virtual_width: u32,
     virtual_height: u32,
     image: RgbaImage,
@@ -42,9 +137,16 @@ pub struct ImageRenderer<R: Borrow<ImageImageRegistry>> {
     supersampling: u32,
     font: FontArc,
     image_registry: R,
+    font_registry: F,
+    stroke_style: StrokeStyle,
+    transforms: Vec<DAffine2>,
+    clip_masks: Vec<GrayImage>,
+    blend_mode: BlendMode,
+    layers: Vec<Layer>,
 }
 
-impl<R: Borrow<ImageImageRegistry>> ImageRenderer<R> {
+impl<R: Borrow<ImageImageRegistry>, F: Borrow<ImageFontRegistry>> ImageRenderer<R, F> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         width: u32,
         height: u32,
@@ -53,6 +155,7 @@ impl<R: Borrow<ImageImageRegistry>> ImageRenderer<R> {
         supersampling: u32,
         font: FontArc,
         image_registry: R,
+        font_registry: F,
     ) -> Self {
         Self {
             virtual_width: width,
@@ -63,6 +166,12 @@ impl<R: Borrow<ImageImageRegistry>> ImageRenderer<R> {
             supersampling,
             font,
             image_registry,
+            font_registry,
+            stroke_style: StrokeStyle::default(),
+            transforms: Vec::new(),
+            clip_masks: Vec::new(),
+            blend_mode: BlendMode::default(),
+            layers: Vec::new(),
         }
     }
 
@@ -82,6 +191,36 @@ impl<R: Borrow<ImageImageRegistry>> ImageRenderer<R> {
         self.image_registry = image_registry;
     }
 
+    pub fn get_font_registry(&self) -> &F {
+        &self.font_registry
+    }
+
+    pub fn set_font_registry(&mut self, font_registry: F) {
+        self.font_registry = font_registry;
+    }
+
+    /// Returns the registered font `style` names, or the font of the renderer,
+    /// followed by its registered fallback fonts.
+    fn get_style_fonts(&self, style: &TextStyle) -> Vec<FontArc> {
+        let font_registry = self.font_registry.borrow();
+
+        let font = style
+            .font_name
+            .as_ref()
+            .and_then(|font_name| font_registry.get_font(font_name))
+            .unwrap_or(&self.font);
+
+        std::iter::once(font)
+            .chain(
+                style
+                    .fallback_font_names
+                    .iter()
+                    .filter_map(|font_name| font_registry.get_font(font_name)),
+            )
+            .cloned()
+            .collect::<Vec<FontArc>>()
+    }
+
     fn get_supersampled_width(&self) -> u32 {
         self.virtual_width * self.supersampling
     }
@@ -90,22 +229,30 @@ impl<R: Borrow<ImageImageRegistry>> ImageRenderer<R> {
         self.virtual_height * self.supersampling
     }
 
-    fn map_value(&self, value: f64) -> f64 {
-        value * self.scale * self.supersampling as f64
+    /// Returns the transform from the current local coordinates to
+    /// supersampled pixels.
+    fn get_pixel_transform(&self) -> DAffine2 {
+        let target = dvec2(
+            self.get_supersampled_width() as f64,
+            self.get_supersampled_height() as f64,
+        ) * self.scaling_target;
+
+        DAffine2::from_translation(target)
+            * DAffine2::from_scale(DVec2::splat(self.scale))
+            * DAffine2::from_translation(-target)
+            * DAffine2::from_scale(DVec2::splat(self.supersampling as f64))
+            * self.get_transform()
     }
 
-    fn map_x(&self, x: f64) -> f64 {
-        let target_x = self.get_supersampled_width() as f64 * self.scaling_target.x;
-        (x * self.supersampling as f64 - target_x) * self.scale + target_x
-    }
+    /// Returns whether the current transform only translates and uniformly
+    /// scales, which text can be drawn with directly.
+    fn is_axis_aligned(&self) -> bool {
+        let matrix = self.get_transform().matrix2;
 
-    fn map_y(&self, y: f64) -> f64 {
-        let target_y = self.get_supersampled_height() as f64 * self.scaling_target.y;
-        (y * self.supersampling as f64 - target_y) * self.scale + target_y
-    }
-
-    fn map_dvec2(&self, v: DVec2) -> DVec2 {
-        dvec2(self.map_x(v.x), self.map_y(v.y))
+        matrix.x_axis.y == 0.0
+            && matrix.y_axis.x == 0.0
+            && matrix.x_axis.x == matrix.y_axis.y
+            && matrix.x_axis.x > 0.0
     }
 
     pub fn reset(&mut self) {
@@ -174,210 +321,494 @@ impl<R: Borrow<ImageImageRegistry>> ImageRenderer<R> {
             .collect::<Vec<DVec2>>()
     }
 
-    fn get_unique_integer_points(&self, points: &[DVec2]) -> Vec<IVec2> {
-        points
+    /// Returns contours given in local coordinates in supersampled pixels.
+    fn get_pixel_contours(&self, contours: &[Vec<DVec2>]) -> Vec<Vec<DVec2>> {
+        let transform = self.get_pixel_transform();
+
+        contours
             .iter()
-            .map(|point| point.round().as_ivec2())
-            .unique()
-            .collect::<Vec<IVec2>>()
+            .map(|contour| {
+                contour
+                    .iter()
+                    .copied()
+                    .map(|point| transform.transform_point2(point))
+                    .collect::<Vec<DVec2>>()
+            })
+            .collect::<Vec<Vec<DVec2>>>()
+    }
+
+    /// Fills contours given in local coordinates.
+    fn fill_contours(&mut self, contours: &[Vec<DVec2>], fill_rule: FillRule, paint: &Paint) {
+        let inverse_transform = self.get_pixel_transform().inverse();
+        let contours = self.get_pixel_contours(contours);
+        let sampler = PaintSampler::new(paint);
+        let mut target = DrawTarget::new(&mut self.image, self.clip_masks.last(), self.blend_mode);
+        let dimensions = (target.width(), target.height());
+
+        for_each_pixel_inside(&contours, fill_rule, dimensions, |x, y| {
+            let color = sampler
+                .sample(inverse_transform.transform_point2(dvec2(x as f64 + 0.5, y as f64 + 0.5)));
+
+            target.draw_pixel(x, y, color);
+        });
     }
 
+    /// Pushes a clip mask of the contours given in local coordinates, inside
+    /// of the current clip mask.
+    fn push_clip_contours(&mut self, contours: &[Vec<DVec2>], fill_rule: FillRule) {
+        let contours = self.get_pixel_contours(contours);
+        let mut clip_mask = GrayImage::new(self.image.width(), self.image.height());
+
+        for_each_pixel_inside(&contours, fill_rule, clip_mask.dimensions(), |x, y| {
+            let is_inside = self
+                .clip_masks
+                .last()
+                .is_none_or(|outer_clip_mask| outer_clip_mask.get_pixel(x, y).0[0] != 0);
+
+            if is_inside {
+                clip_mask.put_pixel(x, y, Luma([255]));
+            }
+        });
+
+        self.clip_masks.push(clip_mask);
+    }
+
+    /// Returns the top left corner of a line of text of the given width, in
+    /// the same coordinates as `position`.
+    fn get_line_origin(
+        &self,
+        font_metrics: &FontMetrics,
+        position: DVec2,
+        anchor: Anchor2D,
+        anchoring: TextAnchoring,
+        width: f64,
+    ) -> DVec2 {
+        let x = match anchor.get_horizontal() {
+            HorizontalAnchor::Left => position.x,
+            HorizontalAnchor::Center => position.x - width / 2.0,
+            HorizontalAnchor::Right => position.x - width,
+        };
+
+        let y = font_metrics.get_baseline(position.y, anchor.get_vertical(), anchoring)
+            - font_metrics.ascent;
+
+        dvec2(x, y)
+    }
+
+    /// Returns the distance the caret moves over a shaped line of text.
+    fn get_line_width(&self, shaper: &Shaper, text: &str, size: f64, letter_spacing: f64) -> f64 {
+        shaper.shape(text, size, letter_spacing).advance
+    }
+
+    /// Returns where each line of `text` starts on its baseline and how wide
+    /// it is, in local coordinates.
+    #[allow(clippy::too_many_arguments)]
+    fn get_line_starts(
+        &self,
+        shaper: &Shaper,
+        font_metrics: &FontMetrics,
+        text: &str,
+        position: DVec2,
+        anchor: Anchor2D,
+        size: f64,
+        style: &TextStyle,
+    ) -> Vec<(DVec2, f64)> {
+        text.split("\n")
+            .enumerate()
+            .map(|(i, line)| {
+                let width = self.get_line_width(shaper, line, size, style.letter_spacing);
+                let origin = self.get_line_origin(
+                    font_metrics,
+                    position + DVec2::Y * size * i as f64,
+                    anchor,
+                    style.anchoring,
+                    width,
+                );
+
+                (origin + DVec2::Y * font_metrics.ascent, width)
+            })
+            .collect::<Vec<(DVec2, f64)>>()
+    }
+
+    /// Returns the outlines of the glyphs of a shaped line of text in local
+    /// coordinates. The contours must be filled with [`FillRule::NonZero`].
+    #[allow(clippy::too_many_arguments)]
+    fn get_line_contours(
+        &self,
+        shaper: &Shaper,
+        font_metrics: &FontMetrics,
+        text: &str,
+        position: DVec2,
+        anchor: Anchor2D,
+        size: f64,
+        style: &TextStyle,
+    ) -> Vec<Vec<DVec2>> {
+        let shaped_line = shaper.shape(text, size, style.letter_spacing);
+
+        let origin = self.get_line_origin(
+            font_metrics,
+            position,
+            anchor,
+            style.anchoring,
+            shaped_line.advance,
+        ) + DVec2::Y * font_metrics.ascent;
+
+        let mut path = Path::new();
+
+        for glyph in shaped_line.glyphs {
+            let font = &shaper.fonts[glyph.font_index];
+
+            push_glyph_outline(
+                &mut path,
+                font,
+                glyph.id,
+                origin + dvec2(glyph.x, glyph.y),
+                get_font_scale(font, size),
+            );
+        }
+
+        path.flatten(self.get_flattening_tolerance())
+            .into_iter()
+            .map(|subpath| subpath.points)
+            .collect::<Vec<Vec<DVec2>>>()
+    }
+
+    /// Returns the coverage of a shaped line of text drawn with its top left
+    /// corner at `(x, y)`, in supersampled pixels, and the pixel the top left
+    /// corner of the coverage belongs at.
+    fn get_text_coverage(
+        &self,
+        shaper: &Shaper,
+        font_metrics: &FontMetrics,
+        shaped_line: &ShapedLine,
+        size: f64,
+        (x, y): (i32, i32),
+    ) -> (GrayImage, i32, i32) {
+        let text_width = shaped_line.advance.ceil().max(0.0) as u32;
+        let text_height = (font_metrics.ascent + font_metrics.descent).ceil() as u32;
+
+        // Glyphs can reach outside of the measured size, by less than the size.
+        let margin = size.ceil() as i32;
+
+        let left = x - margin;
+        let top = y - margin;
+
+        let mut coverage = GrayImage::new(
+            (margin * 2) as u32 + text_width,
+            (margin * 2) as u32 + text_height,
+        );
+
+        for glyph in &shaped_line.glyphs {
+            let glyph_position = point(
+                (x - left) as f32 + glyph.x as f32,
+                (y - top) as f32 + (font_metrics.ascent + glyph.y) as f32,
+            );
+
+            let Some(outlined_glyph) = shaper.fonts[glyph.font_index].outline_glyph(
+                glyph
+                    .id
+                    .with_scale_and_position(size as f32, glyph_position),
+            ) else {
+                continue;
+            };
+
+            let bounds = outlined_glyph.px_bounds();
+
+            outlined_glyph.draw(|glyph_x, glyph_y, value| {
+                let coverage_x = bounds.min.x as i32 + glyph_x as i32;
+                let coverage_y = bounds.min.y as i32 + glyph_y as i32;
+
+                if (0..coverage.width() as i32).contains(&coverage_x)
+                    && (0..coverage.height() as i32).contains(&coverage_y)
+                {
+                    let Luma([previous]) =
+                        coverage.get_pixel_mut(coverage_x as u32, coverage_y as u32);
+                    let previous_value = *previous as f32 / 255.0;
+                    let value = value.clamp(0.0, 1.0);
+
+                    // Overlapping glyphs cover the pixel like stacked layers.
+                    *previous =
+                        ((previous_value + value - previous_value * value) * 255.0).round() as u8;
+                }
+            });
+        }
+
+        (coverage, left, top)
+    }
+
+    /// Draws `color` once onto every pixel by how much `coverage`, with its
+    /// top left corner at `x` and `y`, covers it.
+    fn render_coverage(&mut self, coverage: &GrayImage, x: i32, y: i32, color: Srgba) {
+        let mut target = DrawTarget::new(&mut self.image, self.clip_masks.last(), self.blend_mode);
+        let width = target.width() as i32;
+        let height = target.height() as i32;
+
+        for (coverage_x, coverage_y, Luma([value])) in coverage.enumerate_pixels() {
+            let pixel_x = x + coverage_x as i32;
+            let pixel_y = y + coverage_y as i32;
+
+            if *value > 0 && (0..width).contains(&pixel_x) && (0..height).contains(&pixel_y) {
+                target.draw_pixel(
+                    pixel_x as u32,
+                    pixel_y as u32,
+                    Srgba::new(
+                        color.red,
+                        color.green,
+                        color.blue,
+                        color.alpha * *value as f32 / 255.0,
+                    ),
+                );
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn render_line(
         &mut self,
+        shaper: &Shaper,
+        font_metrics: &FontMetrics,
         text: &str,
         position: DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         color: Srgba,
     ) {
-        let position = self.map_dvec2(position);
-        let size = self.map_value(size);
+        if !self.is_axis_aligned() {
+            let contours =
+                self.get_line_contours(shaper, font_metrics, text, position, anchor, size, style);
 
-        let (text_width, _) = text_size(size as f32, &self.font, text);
+            self.fill_contours(&contours, FillRule::NonZero, &color.into());
 
-        let x = match anchor.get_horizontal() {
-            HorizontalAnchor::Left => position.x,
-            HorizontalAnchor::Center => position.x - text_width as f64 / 2.0,
-            HorizontalAnchor::Right => position.x - text_width as f64,
-        };
+            return;
+        }
 
-        let vertical_anchor = anchor.get_vertical();
+        let transform = self.get_pixel_transform();
+        let position = transform.transform_point2(position);
+        let size = size * transform.matrix2.x_axis.x;
+        let letter_spacing = style.letter_spacing * transform.matrix2.x_axis.x;
+        let font_metrics = font_metrics.scaled(transform.matrix2.x_axis.x);
 
-        let y = match (vertical_anchor.get_context(), vertical_anchor.get_value()) {
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Bottom) => {
-                position.y - size / 1.25
-            }
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Bottom) => position.y,
-            (_, VerticalAnchorValue::Center) => position.y - size / 1.25 / 2.0,
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Top) => position.y,
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Top) => position.y - size / 1.25,
-        };
+        let shaped_line = shaper.shape(text, size, letter_spacing);
+        let origin = self.get_line_origin(
+            &font_metrics,
+            position,
+            anchor,
+            style.anchoring,
+            shaped_line.advance,
+        );
 
-        draw_text_mut(
-            &mut self.image,
-            srgba_to_rgba8(color),
-            x as i32,
-            y as i32,
-            size as f32,
-            &self.font,
-            text,
+        let (coverage, x, y) = self.get_text_coverage(
+            shaper,
+            &font_metrics,
+            &shaped_line,
+            size,
+            (origin.x as i32, origin.y as i32),
         );
+
+        self.render_coverage(&coverage, x, y, color);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn render_line_outline(
         &mut self,
+        shaper: &Shaper,
+        font_metrics: &FontMetrics,
         text: &str,
         position: DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         outline_thickness: f64,
         color: Srgba,
         outline_color: Srgba,
     ) {
-        let position = self.map_dvec2(position);
-        let size = self.map_value(size);
-        let outline_thickness = self.map_value(outline_thickness);
+        let tolerance = self.get_flattening_tolerance();
+        let outline_contours = self
+            .get_line_contours(shaper, font_metrics, text, position, anchor, size, style)
+            .iter()
+            .flat_map(|contour| {
+                stroke_closed(contour, outline_thickness * 2.0, LineJoin::Round, tolerance)
+            })
+            .collect::<Vec<Vec<DVec2>>>();
+
+        self.fill_contours(&outline_contours, FillRule::NonZero, &outline_color.into());
+        self.render_line(
+            shaper,
+            font_metrics,
+            text,
+            position,
+            anchor,
+            size,
+            style,
+            color,
+        );
+    }
+}
 
-        let (text_width, _) = text_size(size as f32, &self.font, text);
+impl<R: Borrow<ImageImageRegistry>, F: Borrow<ImageFontRegistry>> Renderer for ImageRenderer<R, F> {
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
 
-        let x = match anchor.get_horizontal() {
-            HorizontalAnchor::Left => position.x,
-            HorizontalAnchor::Center => position.x - text_width as f64 / 2.0,
-            HorizontalAnchor::Right => position.x - text_width as f64,
-        };
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
 
-        let vertical_anchor = anchor.get_vertical();
+    fn get_stroke_style(&self) -> &StrokeStyle {
+        &self.stroke_style
+    }
 
-        let y = match (vertical_anchor.get_context(), vertical_anchor.get_value()) {
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Bottom) => {
-                position.y - size / 1.25
-            }
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Bottom) => position.y,
-            (_, VerticalAnchorValue::Center) => position.y - size / 1.25 / 2.0,
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Top) => position.y,
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Top) => position.y - size / 1.25,
-        };
+    fn set_stroke_style(&mut self, stroke_style: StrokeStyle) {
+        self.stroke_style = stroke_style;
+    }
 
-        for i in -1..=1 {
-            for j in -1..=1 {
-                if i != 0 || j != 0 {
-                    draw_text_mut(
-                        &mut self.image,
-                        srgba_to_rgba8(outline_color),
-                        (x - i as f64 * outline_thickness).round() as i32,
-                        (y - j as f64 * outline_thickness).round() as i32,
-                        size as f32,
-                        &self.font,
-                        text,
-                    );
-                }
-            }
-        }
+    fn get_transform(&self) -> DAffine2 {
+        self.transforms
+            .last()
+            .copied()
+            .unwrap_or(DAffine2::IDENTITY)
+    }
 
-        draw_text_mut(
-            &mut self.image,
-            srgba_to_rgba8(color),
-            x as i32,
-            y as i32,
-            size as f32,
-            &self.font,
-            text,
-        );
+    fn get_flattening_tolerance(&self) -> f64 {
+        get_transformed_tolerance(self.get_pixel_transform())
     }
-}
 
-impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
-    fn render_point(&mut self, position: DVec2, color: Srgba) {
-        let position = self.map_dvec2(position);
-        let width = self.map_value(1.0);
-        let height = self.map_value(1.0);
+    fn push_transform(&mut self, transform: DAffine2) {
+        self.transforms.push(self.get_transform() * transform);
+    }
 
-        let integer_position = position.round().as_ivec2();
+    fn pop_transform(&mut self) {
+        self.transforms.pop();
+    }
+
+    fn push_clip_rect(
+        &mut self,
+        position: DVec2,
+        width: f64,
+        height: f64,
+        offset: DVec2,
+        rotation: f64,
+    ) {
+        let base_points = self.get_base_points(position, width, height);
+        let offset_vec = self.get_offset_vec(width, height, offset);
+        let offset_points = self.get_offset_points(&base_points, offset_vec);
+        let rotated_points = self.get_rotated_points(&offset_points, position, rotation);
 
-        let integer_width = width.round() as u32;
-        let integer_height = height.round() as u32;
+        self.push_clip_contours(&[rotated_points], FillRule::NonZero);
+    }
 
-        if integer_width > 0 && integer_height > 0 {
-            draw_filled_rect_mut(
-                &mut self.image,
-                Rect::at(integer_position.x, integer_position.y)
-                    .of_size(integer_width, integer_height),
-                srgba_to_rgba8(color),
-            );
-        }
+    fn push_clip_polygon(&mut self, points: &[DVec2]) {
+        self.push_clip_contours(&[points.to_vec()], FillRule::EvenOdd);
     }
 
-    fn render_line(&mut self, start: DVec2, end: DVec2, thickness: f64, color: Srgba) {
-        let start = self.map_dvec2(start);
-        let end = self.map_dvec2(end);
-
-        let thickness = self.map_value(thickness);
-        let offset = thickness / 2.0;
-        let normal = DVec2::from_angle((end - start).to_angle() + FRAC_PI_2);
-
-        let points = vec![
-            start + normal * offset,
-            start - normal * offset,
-            end - normal * offset,
-            end + normal * offset,
-        ];
-
-        let integer_points = self
-            .get_unique_integer_points(&points)
-            .iter()
-            .map(|integer_point| Point::new(integer_point.x, integer_point.y))
-            .collect::<Vec<Point<i32>>>();
+    fn push_clip_path(&mut self, path: &Path, fill_rule: FillRule) {
+        let contours = path
+            .flatten(self.get_flattening_tolerance())
+            .into_iter()
+            .map(|subpath| subpath.points)
+            .collect::<Vec<Vec<DVec2>>>();
 
-        if integer_points.len() == 1 {
-            let integer_point = integer_points.first().unwrap();
+        self.push_clip_contours(&contours, fill_rule);
+    }
 
-            self.render_point(dvec2(integer_point.x as f64, integer_point.y as f64), color);
-        } else {
-            draw_polygon_mut(&mut self.image, &integer_points, srgba_to_rgba8(color));
-        }
+    fn pop_clip(&mut self) {
+        self.clip_masks.pop();
     }
 
-    fn render_circle(&mut self, position: DVec2, radius: f64, color: Srgba) {
-        let position = self.map_dvec2(position).round().as_ivec2();
-        let radius = self.map_value(radius).round() as u32;
+    fn begin_layer(&mut self, opacity: f32, blend_mode: BlendMode) {
+        let layer_image = self.transparent();
+        let parent_image = std::mem::replace(&mut self.image, layer_image);
 
-        draw_filled_circle_mut(
-            &mut self.image,
-            position.into(),
-            radius as i32,
-            srgba_to_rgba8(color),
-        );
+        self.layers.push(Layer {
+            parent_image,
+            opacity,
+            blend_mode,
+        });
     }
 
-    fn render_circle_lines(&mut self, position: DVec2, radius: f64, thickness: f64, color: Srgba) {
-        let position = self.map_dvec2(position).round().as_ivec2();
-        let radius = self.map_value(radius).round();
-        let thickness = self.map_value(thickness).round();
+    fn end_layer(&mut self) {
+        let Some(layer) = self.layers.pop() else {
+            return;
+        };
 
-        let mut circle_renderer = ImageRenderer::new(
-            2 * radius as u32 + 1,
-            2 * radius as u32 + 1,
-            1.0,
-            DVec2::ZERO,
-            1,
-            self.font.clone(),
-            ImageImageRegistry::default(),
+        let layer_image = std::mem::replace(&mut self.image, layer.parent_image);
+        let mut target = DrawTarget::new(&mut self.image, self.clip_masks.last(), layer.blend_mode);
+
+        for (x, y, pixel) in layer_image.enumerate_pixels() {
+            if pixel.0[3] > 0 {
+                let color = rgba8_to_srgba(*pixel);
+
+                target.draw_pixel(
+                    x,
+                    y,
+                    Srgba::new(
+                        color.red,
+                        color.green,
+                        color.blue,
+                        color.alpha * layer.opacity,
+                    ),
+                );
+            }
+        }
+    }
+
+    fn render_point(&mut self, position: DVec2, color: Srgba) {
+        let points = self.get_base_points(position, 1.0, 1.0);
+
+        self.fill_contours(&[points], FillRule::NonZero, &color.into());
+    }
+
+    fn render_line(&mut self, start: DVec2, end: DVec2, thickness: f64, color: Srgba) {
+        let contours = stroke_with_style(
+            &[start, end],
+            false,
+            thickness,
+            LineJoin::default(),
+            LineCap::Butt,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
 
-        circle_renderer.render_circle(dvec2(radius, radius), radius, color);
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
 
-        circle_renderer.render_circle(
-            dvec2(radius, radius),
-            radius - thickness,
-            Srgba::new(0.0, 0.0, 0.0, 0.0),
+    fn render_polyline(
+        &mut self,
+        points: &[DVec2],
+        thickness: f64,
+        join: LineJoin,
+        cap: LineCap,
+        color: Srgba,
+    ) {
+        let contours = stroke_with_style(
+            points,
+            false,
+            thickness,
+            join,
+            cap,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
 
-        overlay(
-            &mut self.image,
-            &circle_renderer.render_image_onto(circle_renderer.transparent()),
-            (position.x - radius as i32) as i64,
-            (position.y - radius as i32) as i64,
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_circle(&mut self, position: DVec2, radius: f64, paint: Paint) {
+        let points = get_elliptical_arc_points(
+            position,
+            DVec2::splat(radius),
+            0.0,
+            0.0,
+            TAU,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
+    }
+
+    fn render_circle_lines(&mut self, position: DVec2, radius: f64, thickness: f64, color: Srgba) {
+        self.render_arc_lines(position, radius, 0.0, 0, TAU, thickness, color);
     }
 
     fn render_arc(
@@ -387,15 +818,12 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         rotation: f64,
         sides: u8,
         arc: f64,
-        color: Srgba,
+        paint: Paint,
     ) {
         if arc == 0.0 {
             return;
         }
 
-        let position = self.map_dvec2(position);
-        let radius = self.map_value(radius);
-
         let points = once(position)
             .chain((0..sides).map(|i| {
                 position
@@ -403,19 +831,7 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
             }))
             .collect::<Vec<DVec2>>();
 
-        let integer_points = self
-            .get_unique_integer_points(&points)
-            .iter()
-            .map(|integer_point| Point::new(integer_point.x, integer_point.y))
-            .collect::<Vec<Point<i32>>>();
-
-        if integer_points.len() == 1 {
-            let integer_point = integer_points.first().unwrap();
-
-            self.render_point(dvec2(integer_point.x as f64, integer_point.y as f64), color);
-        } else {
-            draw_polygon_mut(&mut self.image, &integer_points, srgba_to_rgba8(color));
-        }
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
     }
 
     fn render_arc_lines(
@@ -432,34 +848,120 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
             return;
         }
 
-        let position = self.map_dvec2(position).round().as_ivec2();
-        let radius = self.map_value(radius).round();
-        let thickness = self.map_value(thickness).round();
+        let contours = get_arc_lines_contours(
+            position,
+            radius,
+            rotation,
+            sides,
+            arc,
+            thickness,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
+        );
 
-        let mut circle_renderer = ImageRenderer::new(
-            2 * radius as u32 + 1,
-            2 * radius as u32 + 1,
-            1.0,
-            DVec2::ZERO,
-            1,
-            self.font.clone(),
-            ImageImageRegistry::default(),
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_ellipse(&mut self, position: DVec2, radii: DVec2, rotation: f64, paint: Paint) {
+        self.render_elliptical_arc(position, radii, rotation, 0.0, TAU, paint);
+    }
+
+    fn render_ellipse_lines(
+        &mut self,
+        position: DVec2,
+        radii: DVec2,
+        rotation: f64,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let mut points = get_elliptical_arc_points(
+            position,
+            radii,
+            rotation,
+            0.0,
+            TAU,
+            self.get_flattening_tolerance(),
+        );
+        points.pop();
+
+        let contours = stroke_with_style(
+            &points,
+            true,
+            thickness,
+            LineJoin::default(),
+            LineCap::Butt,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
 
-        circle_renderer.render_arc(dvec2(radius, radius), radius, rotation, sides, arc, color);
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_elliptical_arc(
+        &mut self,
+        position: DVec2,
+        radii: DVec2,
+        rotation: f64,
+        start_angle: f64,
+        arc: f64,
+        paint: Paint,
+    ) {
+        let points = once(position)
+            .chain(get_elliptical_arc_points(
+                position,
+                radii,
+                rotation,
+                start_angle,
+                arc,
+                self.get_flattening_tolerance(),
+            ))
+            .collect::<Vec<DVec2>>();
+
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
+    }
 
-        circle_renderer.render_circle(
-            dvec2(radius, radius),
-            radius - thickness,
-            Srgba::new(0.0, 0.0, 0.0, 0.0),
+    fn render_elliptical_arc_lines(
+        &mut self,
+        position: DVec2,
+        radii: DVec2,
+        rotation: f64,
+        start_angle: f64,
+        arc: f64,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let points = get_elliptical_arc_points(
+            position,
+            radii,
+            rotation,
+            start_angle,
+            arc,
+            self.get_flattening_tolerance(),
         );
 
-        overlay(
-            &mut self.image,
-            &circle_renderer.render_image_onto(circle_renderer.transparent()),
-            (position.x - radius as i32) as i64,
-            (position.y - radius as i32) as i64,
+        let contours = stroke_with_style(
+            &points,
+            false,
+            thickness,
+            LineJoin::default(),
+            LineCap::Butt,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn measure_text(&self, text: &str, size: f64, style: &TextStyle) -> TextMetrics {
+        let fonts = self.get_style_fonts(style);
+        let shaper = Shaper::new(&fonts);
+
+        get_text_font_metrics(&shaper, text, size).get_text_metrics(
+            size,
+            text.split("\n")
+                .map(|line| self.get_line_width(&shaper, line, size, style.letter_spacing))
+                .collect::<Vec<f64>>(),
+        )
     }
 
     fn render_text(
@@ -468,17 +970,45 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         position: DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         color: Srgba,
     ) {
+        if let Some(transform) = style.get_anchored_transform(position) {
+            self.push_transform(transform);
+            self.render_text(text, position, anchor, size, &style.untransformed(), color);
+            self.pop_transform();
+
+            return;
+        }
+
+        let fonts = self.get_style_fonts(style);
+        let shaper = Shaper::new(&fonts);
+        let font_metrics = get_text_font_metrics(&shaper, text, size);
+        let line_starts =
+            self.get_line_starts(&shaper, &font_metrics, text, position, anchor, size, style);
+
+        render_text_background(self, style, &font_metrics, &line_starts);
+
         for (i, line) in text.split("\n").enumerate() {
             self.render_line(
+                &shaper,
+                &font_metrics,
                 line,
                 position + DVec2::Y * size * i as f64,
                 anchor,
                 size,
+                style,
                 color,
             );
         }
+
+        render_text_decorations(
+            self,
+            style,
+            &get_decoration_metrics(&fonts[0], size),
+            &line_starts,
+            color,
+        );
     }
 
     fn render_text_outline(
@@ -487,21 +1017,58 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         position: DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         outline_thickness: f64,
         color: Srgba,
         outline_color: Srgba,
     ) {
+        if let Some(transform) = style.get_anchored_transform(position) {
+            self.push_transform(transform);
+            self.render_text_outline(
+                text,
+                position,
+                anchor,
+                size,
+                &style.untransformed(),
+                outline_thickness,
+                color,
+                outline_color,
+            );
+            self.pop_transform();
+
+            return;
+        }
+
+        let fonts = self.get_style_fonts(style);
+        let shaper = Shaper::new(&fonts);
+        let font_metrics = get_text_font_metrics(&shaper, text, size);
+        let line_starts =
+            self.get_line_starts(&shaper, &font_metrics, text, position, anchor, size, style);
+
+        render_text_background(self, style, &font_metrics, &line_starts);
+
         for (i, line) in text.split("\n").enumerate() {
             self.render_line_outline(
+                &shaper,
+                &font_metrics,
                 line,
                 position + DVec2::Y * size * i as f64,
                 anchor,
                 size,
+                style,
                 outline_thickness,
                 color,
                 outline_color,
             );
         }
+
+        render_text_decorations(
+            self,
+            style,
+            &get_decoration_metrics(&fonts[0], size),
+            &line_starts,
+            color,
+        );
     }
 
     fn render_rectangle(
@@ -511,30 +1078,14 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         height: f64,
         offset: DVec2,
         rotation: f64,
-        color: Srgba,
+        paint: Paint,
     ) {
-        let position = self.map_dvec2(position);
-        let width = self.map_value(width) - 1.0;
-        let height = self.map_value(height) - 1.0;
-
         let base_points = self.get_base_points(position, width, height);
         let offset_vec = self.get_offset_vec(width, height, offset);
         let offset_points = self.get_offset_points(&base_points, offset_vec);
         let rotated_points = self.get_rotated_points(&offset_points, position, rotation);
 
-        let integer_points = self
-            .get_unique_integer_points(&rotated_points)
-            .iter()
-            .map(|integer_point| Point::new(integer_point.x, integer_point.y))
-            .collect::<Vec<Point<i32>>>();
-
-        if integer_points.len() == 1 {
-            let integer_point = integer_points.first().unwrap();
-
-            self.render_point(dvec2(integer_point.x as f64, integer_point.y as f64), color);
-        } else {
-            draw_polygon_mut(&mut self.image, &integer_points, srgba_to_rgba8(color));
-        }
+        self.fill_contours(&[rotated_points], FillRule::NonZero, &paint);
     }
 
     fn render_rectangle_lines(
@@ -547,90 +1098,67 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         thickness: f64,
         color: Srgba,
     ) {
-        let position = self.map_dvec2(position);
-        let width = self.map_value(width) - 1.0;
-        let height = self.map_value(height) - 1.0;
-        let thickness = self.map_value(thickness);
-
-        let base_points = self.get_base_points(position, width, height);
-        let offset_vec = self.get_offset_vec(width, height, offset);
-        let offset_points = self.get_offset_points(&base_points, offset_vec);
-        let rotated_points = self.get_rotated_points(&offset_points, position, rotation);
-
-        let integer_points = self
-            .get_unique_integer_points(&rotated_points)
-            .iter()
-            .map(|integer_point| Point::new(integer_point.x, integer_point.y))
-            .collect::<Vec<Point<i32>>>();
-
-        let min_x = integer_points
-            .iter()
-            .map(|integer_point| integer_point.x)
-            .min()
-            .unwrap();
-        let max_x = integer_points
-            .iter()
-            .map(|integer_point| integer_point.x)
-            .max()
-            .unwrap();
-
-        let min_y = integer_points
-            .iter()
-            .map(|integer_point| integer_point.y)
-            .min()
-            .unwrap();
-        let max_y = integer_points
-            .iter()
-            .map(|integer_point| integer_point.y)
-            .max()
-            .unwrap();
-
-        let min_vec = ivec2(min_x, min_y).as_dvec2();
-
-        let renderer_width = max_x - min_x + 1;
-        let renderer_height = max_y - min_y + 1;
-
-        let mut rectangle_renderer = ImageRenderer::new(
-            renderer_width as u32,
-            renderer_height as u32,
-            1.0,
-            DVec2::ZERO,
-            1,
-            self.font.clone(),
-            ImageImageRegistry::default(),
-        );
-
-        rectangle_renderer.render_rectangle(
-            position - min_vec,
-            width + 1.0,
-            height + 1.0,
+        self.render_rounded_rectangle_lines(
+            position,
+            width,
+            height,
             offset,
             rotation,
+            CornerRadii::uniform(0.0),
+            thickness,
             color,
         );
+    }
 
-        let midpoint = rotated_points
-            .iter()
-            .copied()
-            .map(|rotated_point| rotated_point - min_vec)
-            .sum::<DVec2>()
-            / 4.0;
-
-        rectangle_renderer.render_rectangle(
-            midpoint,
-            width + 1.0 - 2.0 * thickness,
-            height + 1.0 - 2.0 * thickness,
-            DVec2::splat(0.5),
+    fn render_rounded_rectangle(
+        &mut self,
+        position: DVec2,
+        width: f64,
+        height: f64,
+        offset: DVec2,
+        rotation: f64,
+        radii: CornerRadii,
+        paint: Paint,
+    ) {
+        let size = dvec2(width, height);
+
+        let points = get_rounded_rectangle_points(
+            position,
+            position - size * offset,
+            size,
             rotation,
-            Srgba::new(0.0, 0.0, 0.0, 0.0),
+            radii,
+            self.get_flattening_tolerance(),
         );
 
-        overlay(
-            &mut self.image,
-            &rectangle_renderer.render_image_onto(rectangle_renderer.transparent()),
-            min_x as i64,
-            min_y as i64,
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
+    }
+
+    fn render_rounded_rectangle_lines(
+        &mut self,
+        position: DVec2,
+        width: f64,
+        height: f64,
+        offset: DVec2,
+        rotation: f64,
+        radii: CornerRadii,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let size = dvec2(width, height);
+
+        let contours = get_rounded_rectangle_lines_contours(
+            position,
+            position - size * offset,
+            size,
+            rotation,
+            radii,
+            thickness,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
     }
 
     fn render_equilateral_triangle(
@@ -638,28 +1166,13 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         position: DVec2,
         radius: f64,
         rotation: f64,
-        color: Srgba,
+        paint: Paint,
     ) {
-        let position = self.map_dvec2(position);
-        let radius = self.map_value(radius);
-
         let points = (0..3)
             .map(|i| position + radius * DVec2::from_angle(i as f64 * 2.0 * PI / 3.0 + rotation))
             .collect::<Vec<DVec2>>();
 
-        let integer_points = self
-            .get_unique_integer_points(&points)
-            .iter()
-            .map(|integer_point| Point::new(integer_point.x, integer_point.y))
-            .collect::<Vec<Point<i32>>>();
-
-        if integer_points.len() == 1 {
-            let integer_point = integer_points.first().unwrap();
-
-            self.render_point(dvec2(integer_point.x as f64, integer_point.y as f64), color);
-        } else {
-            draw_polygon_mut(&mut self.image, &integer_points, srgba_to_rgba8(color));
-        }
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
     }
 
     fn render_equilateral_triangle_lines(
@@ -670,76 +1183,147 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         thickness: f64,
         color: Srgba,
     ) {
-        let position = self.map_dvec2(position);
-        let radius = self.map_value(radius);
-        let thickness = self.map_value(thickness);
+        self.render_regular_polygon_lines(position, radius, rotation, 3, thickness, color);
+    }
 
-        let points = (0..3)
-            .map(|i| position + radius * DVec2::from_angle(i as f64 * 2.0 * PI / 3.0 + rotation))
-            .collect::<Vec<DVec2>>();
+    fn render_polygon(&mut self, points: &[DVec2], holes: &[Vec<DVec2>], paint: Paint) {
+        let contours = once(points.to_vec())
+            .chain(holes.iter().cloned())
+            .collect::<Vec<Vec<DVec2>>>();
 
-        let integer_points = self
-            .get_unique_integer_points(&points)
-            .iter()
-            .map(|integer_point| Point::new(integer_point.x, integer_point.y))
-            .collect::<Vec<Point<i32>>>();
+        self.fill_contours(&contours, FillRule::EvenOdd, &paint);
+    }
 
-        let min_x = integer_points
-            .iter()
-            .map(|integer_point| integer_point.x)
-            .min()
-            .expect("triangles have more than 0 points");
-        let max_x = integer_points
-            .iter()
-            .map(|integer_point| integer_point.x)
-            .max()
-            .expect("triangles have more than 0 points");
-        let min_y = integer_points
-            .iter()
-            .map(|integer_point| integer_point.y)
-            .min()
-            .expect("triangles have more than 0 points");
-        let max_y = integer_points
-            .iter()
-            .map(|integer_point| integer_point.y)
-            .max()
-            .expect("triangles have more than 0 points");
+    fn render_polygon_lines(
+        &mut self,
+        points: &[DVec2],
+        holes: &[Vec<DVec2>],
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let contours = once(points)
+            .chain(holes.iter().map(Vec::as_slice))
+            .flat_map(|contour| {
+                stroke_with_style(
+                    contour,
+                    true,
+                    thickness,
+                    LineJoin::default(),
+                    LineCap::Butt,
+                    &self.stroke_style,
+                    self.get_flattening_tolerance(),
+                )
+            })
+            .collect::<Vec<Vec<DVec2>>>();
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
 
-        let min_point = ivec2(min_x, min_y);
+    fn render_path_fill(&mut self, path: &Path, fill_rule: FillRule, paint: Paint) {
+        let contours = path
+            .flatten(self.get_flattening_tolerance())
+            .into_iter()
+            .map(|subpath| subpath.points)
+            .collect::<Vec<Vec<DVec2>>>();
 
-        let renderer_width = (max_x - min_x + 1) as u32;
-        let renderer_height = (max_y - min_y + 1) as u32;
+        self.fill_contours(&contours, fill_rule, &paint);
+    }
 
-        let mut triangle_renderer = ImageRenderer::new(
-            renderer_width,
-            renderer_height,
-            1.0,
-            DVec2::ZERO,
-            1,
-            self.font.clone(),
-            ImageImageRegistry::default(),
-        );
+    fn render_path_stroke(
+        &mut self,
+        path: &Path,
+        thickness: f64,
+        join: LineJoin,
+        cap: LineCap,
+        color: Srgba,
+    ) {
+        let contours = path
+            .flatten(self.get_flattening_tolerance())
+            .into_iter()
+            .flat_map(|subpath| {
+                stroke_with_style(
+                    &subpath.points,
+                    subpath.closed,
+                    thickness,
+                    join,
+                    cap,
+                    &self.stroke_style,
+                    self.get_flattening_tolerance(),
+                )
+            })
+            .collect::<Vec<Vec<DVec2>>>();
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
 
-        triangle_renderer.render_equilateral_triangle(
-            (position - min_point.as_dvec2()).round(),
-            radius,
-            rotation,
-            color,
-        );
+    fn render_regular_polygon(
+        &mut self,
+        position: DVec2,
+        radius: f64,
+        rotation: f64,
+        sides: u8,
+        paint: Paint,
+    ) {
+        let points = get_regular_polygon_points(position, radius, rotation, sides);
 
-        triangle_renderer.render_equilateral_triangle(
-            (position - min_point.as_dvec2()).round(),
-            radius - thickness,
-            rotation,
-            Srgba::new(0.0, 0.0, 0.0, 0.0),
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
+    }
+
+    fn render_regular_polygon_lines(
+        &mut self,
+        position: DVec2,
+        radius: f64,
+        rotation: f64,
+        sides: u8,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let points = get_regular_polygon_points(position, radius, rotation, sides);
+        let contours = get_inset_lines_contours(
+            position,
+            &points,
+            thickness,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
 
-        overlay(
-            &mut self.image,
-            &triangle_renderer.render_image_onto(triangle_renderer.transparent()),
-            min_x as i64,
-            min_y as i64,
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_star(
+        &mut self,
+        position: DVec2,
+        outer_radius: f64,
+        inner_radius: f64,
+        rotation: f64,
+        points: u8,
+        paint: Paint,
+    ) {
+        let points = get_star_points(position, outer_radius, inner_radius, rotation, points);
+
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
+    }
+
+    fn render_star_lines(
+        &mut self,
+        position: DVec2,
+        outer_radius: f64,
+        inner_radius: f64,
+        rotation: f64,
+        points: u8,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let points = get_star_points(position, outer_radius, inner_radius, rotation, points);
+        let contours = get_inset_lines_contours(
+            position,
+            &points,
+            thickness,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
     }
 
     fn render_image(
@@ -751,28 +1335,70 @@ impl<R: Borrow<ImageImageRegistry>> Renderer for ImageRenderer<R> {
         offset: ::glam::DVec2,
         rotation: f64,
     ) {
-        let position = self.map_dvec2(position);
-        let width = self.map_value(width) - 1.0;
-        let height = self.map_value(height) - 1.0;
+        let transform = self.get_pixel_transform();
+        let size = dvec2(width, height);
+        let top_left = position - size * offset;
+
+        let [top_left, top_right, bottom_right, bottom_left] =
+            [DVec2::ZERO, DVec2::X, DVec2::ONE, DVec2::Y].map(|uv| {
+                let point = rotate_point_around(top_left + size * uv, position, rotation);
+
+                MeshVertex::new(
+                    transform.transform_point2(point),
+                    uv,
+                    Srgba::new(1.0, 1.0, 1.0, 1.0),
+                )
+            });
 
         if let Some(image) = self.image_registry.borrow().get_image(image_name) {
-            let resized_image = resize(image, width as u32, height as u32, FilterType::Nearest);
-            let mut base_image = self.transparent();
-            overlay(
-                &mut base_image,
-                &resized_image,
-                (position.x - width * offset.x) as i64,
-                (position.y - height * offset.y) as i64,
-            );
-            let rotated_image = rotate(
-                &base_image,
-                (position.x as f32, position.y as f32),
-                rotation as f32,
-                Interpolation::Nearest,
-                Rgba::from([0, 0, 0, 0]),
-            );
+            for triangle in [
+                [top_left, top_right, bottom_right],
+                [top_left, bottom_right, bottom_left],
+            ] {
+                fill_mesh_triangle(
+                    &mut DrawTarget::new(&mut self.image, self.clip_masks.last(), self.blend_mode),
+                    triangle,
+                    Some(image),
+                    sample_nearest,
+                );
+            }
+        }
+    }
 
-            overlay(&mut self.image, &rotated_image, 0, 0);
+    fn render_mesh(&mut self, vertices: &[MeshVertex], indices: &[u32], image_name: Option<&str>) {
+        let texture = match image_name {
+            Some(image_name) => match self.image_registry.borrow().get_image(image_name) {
+                Some(texture) => Some(texture),
+                None => return,
+            },
+            None => None,
+        };
+
+        let transform = self.get_pixel_transform();
+
+        let mapped_vertices = vertices
+            .iter()
+            .map(|vertex| MeshVertex {
+                position: transform.transform_point2(vertex.position),
+                ..*vertex
+            })
+            .collect::<Vec<MeshVertex>>();
+
+        for triangle in indices.chunks_exact(3) {
+            let triangle_vertices = [
+                mapped_vertices.get(triangle[0] as usize),
+                mapped_vertices.get(triangle[1] as usize),
+                mapped_vertices.get(triangle[2] as usize),
+            ];
+
+            if let [Some(a), Some(b), Some(c)] = triangle_vertices {
+                fill_mesh_triangle(
+                    &mut DrawTarget::new(&mut self.image, self.clip_masks.last(), self.blend_mode),
+                    [*a, *b, *c],
+                    texture,
+                    sample_bilinear,
+                );
+            }
         }
     }
 }
@@ -788,3 +1414,219 @@ fn rotate_point_around(point: DVec2, axis: DVec2, theta: f64) -> DVec2 {
     let new_relative = DVec2::from_angle(new_relative_theta) * relative.length();
     new_relative + axis
 }
+
+/// Calls `f` with every pixel of an image of the given dimensions whose
+/// center is inside the contours.
+fn for_each_pixel_inside(
+    contours: &[Vec<DVec2>],
+    fill_rule: FillRule,
+    (width, height): (u32, u32),
+    mut f: impl FnMut(u32, u32),
+) {
+    let Some((min_y, max_y)) = contours
+        .iter()
+        .flatten()
+        .map(|point| point.y)
+        .minmax()
+        .into_option()
+    else {
+        return;
+    };
+
+    let start_y = (min_y.floor() as i64).max(0);
+    let end_y = (max_y.ceil() as i64).min(height as i64);
+
+    let mut scanner = Scanner::new(contours, fill_rule);
+
+    for y in start_y..end_y {
+        for (start_x, end_x) in scanner.get_spans(y as f64 + 0.5) {
+            let start_x = ((start_x - 0.5).ceil() as i64).max(0);
+            let end_x = ((end_x - 0.5).ceil() as i64).min(width as i64);
+
+            for x in start_x..end_x {
+                f(x as u32, y as u32);
+            }
+        }
+    }
+}
+
+fn fill_mesh_triangle(
+    target: &mut DrawTarget,
+    vertices: [MeshVertex; 3],
+    texture: Option<&RgbaImage>,
+    sample: fn(&RgbaImage, DVec2) -> Srgba,
+) {
+    let [a, b, c] = vertices.map(|vertex| vertex.position);
+    let area = (b - a).perp_dot(c - a);
+
+    if area.abs() < f64::EPSILON {
+        return;
+    }
+
+    // Pixels centered exactly on an edge belong to only one of the two
+    // triangles sharing it, so they are drawn once.
+    let owned_edges = [c - b, a - c, b - a].map(|edge| {
+        let edge = edge * area.signum();
+
+        edge.y > 0.0 || (edge.y == 0.0 && edge.x < 0.0)
+    });
+
+    let min = a.min(b).min(c).floor().max(DVec2::ZERO);
+    let max = a
+        .max(b)
+        .max(c)
+        .ceil()
+        .min(dvec2(target.width() as f64, target.height() as f64));
+
+    for y in min.y as u32..max.y as u32 {
+        for x in min.x as u32..max.x as u32 {
+            let point = dvec2(x as f64 + 0.5, y as f64 + 0.5);
+
+            let weights = [
+                (c - b).perp_dot(point - b) / area,
+                (a - c).perp_dot(point - c) / area,
+                (b - a).perp_dot(point - a) / area,
+            ];
+
+            if weights
+                .iter()
+                .zip(owned_edges)
+                .any(|(weight, is_owned)| *weight < 0.0 || (*weight == 0.0 && !is_owned))
+            {
+                continue;
+            }
+
+            let uv = vertices
+                .iter()
+                .zip(weights)
+                .map(|(vertex, weight)| vertex.uv * weight)
+                .sum::<DVec2>();
+
+            let vertex_color = vertices.iter().zip(weights).fold(
+                Srgba::new(0.0, 0.0, 0.0, 0.0),
+                |sum, (vertex, weight)| {
+                    let weight = weight as f32;
+
+                    Srgba::new(
+                        sum.red + vertex.color.red * weight,
+                        sum.green + vertex.color.green * weight,
+                        sum.blue + vertex.color.blue * weight,
+                        sum.alpha + vertex.color.alpha * weight,
+                    )
+                },
+            );
+
+            let color = match texture {
+                Some(texture) => {
+                    let texel = sample(texture, uv);
+
+                    Srgba::new(
+                        vertex_color.red * texel.red,
+                        vertex_color.green * texel.green,
+                        vertex_color.blue * texel.blue,
+                        vertex_color.alpha * texel.alpha,
+                    )
+                }
+                None => vertex_color,
+            };
+
+            target.draw_pixel(x, y, color);
+        }
+    }
+}
+
+fn get_texel(texture: &RgbaImage, position: DVec2) -> Srgba {
+    let size = dvec2(texture.width() as f64, texture.height() as f64);
+    let position = position.clamp(DVec2::ZERO, size - 1.0);
+    let Rgba([red, green, blue, alpha]) = *texture.get_pixel(position.x as u32, position.y as u32);
+
+    Srgba::new(red, green, blue, alpha).into_format::<f32, f32>()
+}
+
+fn sample_nearest(texture: &RgbaImage, uv: DVec2) -> Srgba {
+    let size = dvec2(texture.width() as f64, texture.height() as f64);
+
+    get_texel(texture, (uv * size).floor())
+}
+
+fn sample_bilinear(texture: &RgbaImage, uv: DVec2) -> Srgba {
+    let size = dvec2(texture.width() as f64, texture.height() as f64);
+    let position = uv * size - 0.5;
+    let base = position.floor();
+    let fraction = (position - base).as_vec2();
+
+    let texel = |offset_x: f64, offset_y: f64| get_texel(texture, base + dvec2(offset_x, offset_y));
+
+    let top = mix_srgba(texel(0.0, 0.0), texel(1.0, 0.0), fraction.x);
+    let bottom = mix_srgba(texel(0.0, 1.0), texel(1.0, 1.0), fraction.x);
+
+    mix_srgba(top, bottom, fraction.y)
+}
+
+fn mix_srgba(a: Srgba, b: Srgba, t: f32) -> Srgba {
+    Srgba::new(
+        a.red + (b.red - a.red) * t,
+        a.green + (b.green - a.green) * t,
+        a.blue + (b.blue - a.blue) * t,
+        a.alpha + (b.alpha - a.alpha) * t,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_renderer() -> ImageRenderer<ImageImageRegistry, ImageFontRegistry> {
+        ImageRenderer::new(
+            64,
+            64,
+            1.0,
+            DVec2::ZERO,
+            1,
+            FontArc::try_from_slice(include_bytes!("../../examples/roboto.ttf")).unwrap(),
+            ImageImageRegistry::default(),
+            ImageFontRegistry::default(),
+        )
+    }
+
+    fn get_painted_pixel_count(
+        renderer: &ImageRenderer<ImageImageRegistry, ImageFontRegistry>,
+    ) -> usize {
+        renderer
+            .get_image()
+            .pixels()
+            .filter(|Rgba([_, _, _, alpha])| *alpha > 0)
+            .count()
+    }
+
+    #[test]
+    fn stroked_circle_paints_pixels() {
+        let mut renderer = get_renderer();
+
+        renderer.render_circle_lines(
+            DVec2::splat(32.0),
+            16.0,
+            2.0,
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+
+        let circle_count = get_painted_pixel_count(&renderer);
+
+        let mut renderer = get_renderer();
+
+        renderer.render_arc_lines(
+            DVec2::splat(32.0),
+            16.0,
+            0.0,
+            64,
+            TAU,
+            2.0,
+            Srgba::new(1.0, 1.0, 1.0, 1.0),
+        );
+
+        let arc_count = get_painted_pixel_count(&renderer);
+
+        assert!(circle_count > 0);
+        assert!(circle_count.abs_diff(arc_count) < arc_count / 10);
+    }
+}
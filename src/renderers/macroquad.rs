@@ -1,10 +1,84 @@
-use std::borrow::Borrow;
+use std::{borrow::Borrow, collections::HashMap, f64::consts::TAU, iter::once};
 
-use anchor2d::{Anchor2D, HorizontalAnchor, VerticalAnchorContext, VerticalAnchorValue};
-use macroquad::prelude::*;
+use ab_glyph::{Font as _, FontArc};
+use anchor2d::{Anchor2D, HorizontalAnchor};
+use itertools::Itertools;
+use macroquad::{
+    miniquad::{BlendFactor, BlendState, BlendValue, Equation},
+    prelude::*,
+};
 use palette::Srgba;
 
-use crate::{Renderer, image_registries::macroquad_image_registry::MacroquadImageRegistry};
+use crate::{
+    BlendMode, CornerRadii, FillRule, Gradient, GradientKind, LineCap, LineJoin, MeshVertex, Paint,
+    Path, Renderer, StrokeStyle, TextAnchoring, TextMetrics, TextStyle,
+    font_fallback::split_by_font,
+    font_metrics::FontMetrics,
+    font_registries::macroquad_font_registry::MacroquadFontRegistry,
+    geometry::{
+        Region, get_arc_lines_contours, get_elliptical_arc_points, get_inset_lines_contours,
+        get_regular_polygon_points, get_rounded_rectangle_lines_contours,
+        get_rounded_rectangle_points, get_star_points, stroke_closed, stroke_with_style,
+        tessellate, tessellate_intersection,
+    },
+    glyph_outlines::push_glyph_outline,
+    image_registries::macroquad_image_registry::MacroquadImageRegistry,
+    text_decoration::{DecorationMetrics, render_text_background, render_text_decorations},
+};
+
+const MAX_MESH_TRIANGLES: usize = 1024;
+
+/// The decoration metrics of the default font of macroquad at a size of one,
+/// with lines one pixel of the font thick.
+const DEFAULT_DECORATION_METRICS: DecorationMetrics = DecorationMetrics {
+    underline_offset: 128.0 / 2048.0,
+    underline_thickness: 128.0 / 2048.0,
+    strikethrough_offset: -576.0 / 2048.0,
+    strikethrough_thickness: 128.0 / 2048.0,
+};
+
+/// The metrics of the default font of macroquad at a size of one.
+const DEFAULT_FONT_METRICS: FontMetrics = FontMetrics {
+    ascent: 1216.0 / 2048.0,
+    descent: 448.0 / 2048.0,
+    cap_height: 1024.0 / 2048.0,
+};
+
+/// The largest radius, in pixels, that the outlines of text drawn with fonts
+/// whose data is not known are grown to.
+const MAX_DILATION_RADIUS: f32 = 8.0;
+
+/// Returns the parsed data of the default font of macroquad, which text drawn
+/// without a font is outlined with.
+fn get_default_font_data() -> FontArc {
+    FontArc::try_from_slice(include_bytes!("proggy_clean.ttf")).expect("the default font is valid")
+}
+
+fn affine_to_mat4(transform: ::glam::DAffine2) -> Mat4 {
+    let ::glam::DAffine2 {
+        matrix2,
+        translation,
+    } = transform;
+
+    Mat4::from_cols_array(&[
+        matrix2.x_axis.x as f32,
+        matrix2.x_axis.y as f32,
+        0.0,
+        0.0,
+        matrix2.y_axis.x as f32,
+        matrix2.y_axis.y as f32,
+        0.0,
+        0.0,
+        0.0,
+        0.0,
+        1.0,
+        0.0,
+        translation.x as f32,
+        translation.y as f32,
+        0.0,
+        1.0,
+    ])
+}
 
 fn srgba_to_color(srgba: Srgba) -> Color {
     Color {
@@ -15,17 +89,99 @@ fn srgba_to_color(srgba: Srgba) -> Color {
     }
 }
 
+/// What the paint shaders multiply the vertex colors with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Shading {
+    Solid,
+    Gradient,
+    /// The premultiplied colors of a layer texture.
+    Layer,
+    /// The coverage of a layer texture grown by a radius.
+    Dilate,
+}
+
+/// A layer being drawn into, with the projection it is drawn with.
+#[derive(Debug, Clone)]
+struct Layer {
+    render_target: RenderTarget,
+    projection: Mat4,
+    opacity: f32,
+    blend_mode: BlendMode,
+    /// The clips lifted while drawing into the layer, which cut the layer
+    /// when it is drawn.
+    mask_clips: Option<Vec<Clip>>,
+}
+
+/// Draws into a layer with the projection of what the layer is drawn over.
+struct LayerCamera<'a> {
+    layer: &'a Layer,
+}
+
+impl Camera for LayerCamera<'_> {
+    fn matrix(&self) -> Mat4 {
+        self.layer.projection
+    }
+
+    fn depth_enabled(&self) -> bool {
+        false
+    }
+
+    fn render_pass(&self) -> Option<RenderPass> {
+        Some(self.layer.render_target.render_pass.clone())
+    }
+
+    fn viewport(&self) -> Option<(i32, i32, i32, i32)> {
+        None
+    }
+}
+
+/// A pushed clip. Every clip limits drawing to its bounding box with the
+/// scissor rectangle, and clips that the scissor rectangle does not cover
+/// exactly also cut the geometry of every draw. Text, whose geometry macroquad
+/// builds, is drawn into a layer that is cut instead.
+#[derive(Debug, Clone)]
+struct Clip {
+    /// The contours in the coordinates of the renderer.
+    contours: Vec<Vec<::glam::DVec2>>,
+    fill_rule: FillRule,
+    is_rectangular: bool,
+    /// The intersection of the bounding boxes of this and every outer clip,
+    /// as `(x, y, width, height)` in pixels from the top left.
+    scissor: (i32, i32, i32, i32),
+}
+
 #[derive(Debug, Default, Clone)]
-pub struct MacroquadRenderer<R: Borrow<MacroquadImageRegistry>> {
+pub struct MacroquadRenderer<R: Borrow<MacroquadImageRegistry>, F: Borrow<MacroquadFontRegistry>> {
     font: Option<Font>,
+    /// The parsed data of `font`, which text is measured with.
+    font_data: Option<FontArc>,
     image_registry: R,
+    font_registry: F,
+    stroke_style: StrokeStyle,
+    transforms: Vec<::glam::DAffine2>,
+    clips: Vec<Clip>,
+    blend_mode: BlendMode,
+    /// The materials loaded so far, by shading and blend mode.
+    materials: HashMap<(Shading, BlendMode), Material>,
+    layers: Vec<Layer>,
+    /// Render targets of ended layers, to draw following layers into.
+    free_render_targets: Vec<RenderTarget>,
 }
 
-impl<R: Borrow<MacroquadImageRegistry>> MacroquadRenderer<R> {
-    pub fn new(font: Option<Font>, image_registry: R) -> Self {
+impl<R: Borrow<MacroquadImageRegistry>, F: Borrow<MacroquadFontRegistry>> MacroquadRenderer<R, F> {
+    pub fn new(font: Option<Font>, image_registry: R, font_registry: F) -> Self {
         Self {
+            font_data: font.is_none().then(get_default_font_data),
             font,
             image_registry,
+            font_registry,
+            stroke_style: StrokeStyle::default(),
+            transforms: Vec::new(),
+            clips: Vec::new(),
+            blend_mode: BlendMode::default(),
+            materials: HashMap::new(),
+            layers: Vec::new(),
+            free_render_targets: Vec::new(),
         }
     }
 
@@ -33,10 +189,23 @@ impl<R: Borrow<MacroquadImageRegistry>> MacroquadRenderer<R> {
         self.font.as_ref()
     }
 
+    /// Sets the font text is drawn with when no registered font is selected.
+    /// Text drawn with a font set without its data is measured like the
+    /// default font of macroquad.
     pub fn set_font(&mut self, font: Option<Font>) {
+        self.font_data = font.is_none().then(get_default_font_data);
         self.font = font;
     }
 
+    /// Parses the TTF or OTF `bytes` and draws text with the font when no
+    /// registered font is selected.
+    pub fn load_font(&mut self, bytes: &[u8]) -> Result<(), macroquad::Error> {
+        self.set_font(Some(load_ttf_font_from_bytes(bytes)?));
+        self.font_data = FontArc::try_from_vec(bytes.to_vec()).ok();
+
+        Ok(())
+    }
+
     pub fn get_image_registry(&self) -> &R {
         &self.image_registry
     }
@@ -44,18 +213,909 @@ impl<R: Borrow<MacroquadImageRegistry>> MacroquadRenderer<R> {
     pub fn set_image_registry(&mut self, image_registry: R) {
         self.image_registry = image_registry;
     }
+
+    pub fn get_font_registry(&self) -> &F {
+        &self.font_registry
+    }
+
+    pub fn set_font_registry(&mut self, font_registry: F) {
+        self.font_registry = font_registry;
+    }
+
+    /// Returns the registered font `style` names, or the font of the renderer,
+    /// followed by its registered fallback fonts.
+    fn get_style_fonts(&self, style: &TextStyle) -> Vec<ChainFont> {
+        let font_registry = self.font_registry.borrow();
+
+        let font = match style
+            .font_name
+            .as_ref()
+            .filter(|font_name| font_registry.get_font(*font_name).is_some())
+        {
+            Some(font_name) => ChainFont::from_registry(font_registry, font_name),
+            None => ChainFont {
+                font: self.font.clone(),
+                font_data: self.font_data.clone(),
+            },
+        };
+
+        once(font)
+            .chain(
+                style
+                    .fallback_font_names
+                    .iter()
+                    .filter(|font_name| font_registry.get_font(*font_name).is_some())
+                    .map(|font_name| ChainFont::from_registry(font_registry, font_name)),
+            )
+            .collect::<Vec<ChainFont>>()
+    }
+
+    /// Returns whether a clip is pushed that the scissor rectangle does not
+    /// cover exactly, so draws have to be cut to it.
+    fn is_mask_clipped(&self) -> bool {
+        self.clips.iter().any(|clip| !clip.is_rectangular)
+    }
+
+    /// Returns the contours of every clip that the scissor rectangle does not
+    /// cover exactly, in local coordinates, with their fill rules.
+    fn get_mask_clips(&self) -> Vec<(Vec<Vec<::glam::DVec2>>, FillRule)> {
+        let inverse_transform = self.get_transform().inverse();
+
+        self.clips
+            .iter()
+            .filter(|clip| !clip.is_rectangular)
+            .map(|clip| {
+                let contours = clip
+                    .contours
+                    .iter()
+                    .map(|contour| {
+                        contour
+                            .iter()
+                            .copied()
+                            .map(|point| inverse_transform.transform_point2(point))
+                            .collect::<Vec<::glam::DVec2>>()
+                    })
+                    .collect::<Vec<Vec<::glam::DVec2>>>();
+
+                (contours, clip.fill_rule)
+            })
+            .collect::<Vec<(Vec<Vec<::glam::DVec2>>, FillRule)>>()
+    }
+
+    /// Splits the area inside the contours and every clip into triangles.
+    fn tessellate_clipped(
+        &self,
+        contours: &[Vec<::glam::DVec2>],
+        fill_rule: FillRule,
+    ) -> Vec<[::glam::DVec2; 3]> {
+        if !self.is_mask_clipped() {
+            return tessellate(contours, fill_rule);
+        }
+
+        let mask_clips = self.get_mask_clips();
+
+        let regions = once(Region::new(contours, fill_rule))
+            .chain(
+                mask_clips
+                    .iter()
+                    .map(|(contours, fill_rule)| Region::new(contours, *fill_rule)),
+            )
+            .collect::<Vec<Region>>();
+
+        tessellate_intersection(&regions)
+    }
+
+    /// Pushes a clip of the contours given in local coordinates. The
+    /// rectangular flag marks contours that are a single rectangle.
+    fn push_clip_contours(
+        &mut self,
+        contours: &[Vec<::glam::DVec2>],
+        fill_rule: FillRule,
+        is_rectangle: bool,
+    ) {
+        let transform = self.get_transform();
+
+        let contours = contours
+            .iter()
+            .map(|contour| {
+                contour
+                    .iter()
+                    .copied()
+                    .map(|point| transform.transform_point2(point))
+                    .collect::<Vec<::glam::DVec2>>()
+            })
+            .collect::<Vec<Vec<::glam::DVec2>>>();
+
+        let (width, height) = get_target_size();
+        let gl = unsafe { get_internal_gl() };
+        let projection = gl.quad_gl.get_projection_matrix();
+
+        let pixel_points = contours
+            .iter()
+            .flatten()
+            .map(|point| {
+                let ndc = projection.project_point3(vec3(point.x as f32, point.y as f32, 0.0));
+
+                vec2(
+                    (ndc.x + 1.0) / 2.0 * width as f32,
+                    (1.0 - ndc.y) / 2.0 * height as f32,
+                )
+            })
+            .collect::<Vec<Vec2>>();
+
+        let min = pixel_points
+            .iter()
+            .copied()
+            .fold(Vec2::splat(f32::INFINITY), Vec2::min)
+            .round();
+        let max = pixel_points
+            .iter()
+            .copied()
+            .fold(Vec2::splat(f32::NEG_INFINITY), Vec2::max)
+            .round();
+
+        let (min, max) = match self.clips.last() {
+            Some(Clip {
+                scissor: (x, y, width, height),
+                ..
+            }) => {
+                let outer_min = vec2(*x as f32, *y as f32);
+                let outer_max = outer_min + vec2(*width as f32, *height as f32);
+
+                (min.max(outer_min), max.min(outer_max))
+            }
+            None => (min, max),
+        };
+
+        let size = (max - min).max(Vec2::ZERO);
+
+        let is_rectangular = is_rectangle
+            && pixel_points
+                .iter()
+                .zip(pixel_points.iter().cycle().skip(1))
+                .all(|(start, end)| {
+                    (start.x - end.x).abs() < 0.01 || (start.y - end.y).abs() < 0.01
+                });
+
+        let clip = Clip {
+            contours,
+            fill_rule,
+            is_rectangular,
+            scissor: (min.x as i32, min.y as i32, size.x as i32, size.y as i32),
+        };
+
+        gl.quad_gl.scissor(Some(clip.scissor));
+
+        self.clips.push(clip);
+    }
+
+    /// Cuts a mesh triangle to the clips, interpolating the texture
+    /// coordinates and colors of the new vertices.
+    fn clip_mesh_triangle(&self, vertices: [MeshVertex; 3]) -> Vec<MeshVertex> {
+        if !self.is_mask_clipped() {
+            return vertices.to_vec();
+        }
+
+        let [a, b, c] = vertices.map(|vertex| vertex.position);
+        let area = (b - a).perp_dot(c - a);
+
+        if area.abs() < f64::EPSILON {
+            return Vec::new();
+        }
+
+        self.tessellate_clipped(&[vec![a, b, c]], FillRule::NonZero)
+            .into_iter()
+            .flatten()
+            .map(|point| {
+                let weights = [
+                    (c - b).perp_dot(point - b) / area,
+                    (a - c).perp_dot(point - c) / area,
+                    (b - a).perp_dot(point - a) / area,
+                ];
+
+                let uv = vertices
+                    .iter()
+                    .zip(weights)
+                    .map(|(vertex, weight)| vertex.uv * weight)
+                    .sum::<::glam::DVec2>();
+
+                let color = vertices.iter().zip(weights).fold(
+                    Srgba::new(0.0, 0.0, 0.0, 0.0),
+                    |sum, (vertex, weight)| {
+                        let weight = weight as f32;
+
+                        Srgba::new(
+                            sum.red + vertex.color.red * weight,
+                            sum.green + vertex.color.green * weight,
+                            sum.blue + vertex.color.blue * weight,
+                            sum.alpha + vertex.color.alpha * weight,
+                        )
+                    },
+                );
+
+                MeshVertex::new(point, uv, color)
+            })
+            .collect::<Vec<MeshVertex>>()
+    }
+
+    /// Calls `draw` with the clips lifted and draws what it draws through a
+    /// layer cut to the clips, for draws whose geometry cannot be cut to the
+    /// clips directly.
+    fn with_clip_mask(&mut self, draw: impl FnOnce(&mut Self)) {
+        if !self.is_mask_clipped() {
+            draw(self);
+
+            return;
+        }
+
+        self.with_masked_layer(draw);
+        self.end_layer();
+    }
+
+    /// Begins a layer, lifting the clips into it if they cut it, and calls
+    /// `draw` with the normal blend mode to draw into it. The layer is left
+    /// for the caller to end.
+    fn with_masked_layer(&mut self, draw: impl FnOnce(&mut Self)) {
+        let is_mask_clipped = self.is_mask_clipped();
+
+        self.begin_layer(1.0, self.blend_mode);
+
+        if is_mask_clipped && let Some(layer) = self.layers.last_mut() {
+            layer.mask_clips = Some(std::mem::take(&mut self.clips));
+        }
+
+        let blend_mode = std::mem::take(&mut self.blend_mode);
+        draw(self);
+        self.blend_mode = blend_mode;
+    }
+
+    /// Ends the current layer and draws it multiplied by `color`, with its
+    /// coverage grown by `dilation_radius` pixels if given.
+    fn end_layer_with(&mut self, color: Color, dilation_radius: Option<f32>) {
+        let Some(mut layer) = self.layers.pop() else {
+            return;
+        };
+
+        pop_camera_state();
+
+        // The corners of the layer, mapped back through the projection and
+        // the model matrix so the layer covers exactly what it was drawn over.
+        let to_ndc = layer.projection * affine_to_mat4(self.get_transform());
+        let inverse = to_ndc.inverse();
+        let color = Color::new(color.r, color.g, color.b, color.a * layer.opacity);
+
+        let corners = [
+            vec2(0.0, 0.0),
+            vec2(1.0, 0.0),
+            vec2(1.0, 1.0),
+            vec2(0.0, 1.0),
+        ]
+        .map(|uv| {
+            let corner = inverse.project_point3((uv * 2.0 - 1.0).extend(0.0));
+
+            ::glam::dvec2(corner.x as f64, corner.y as f64)
+        });
+
+        let triangles = match layer.mask_clips.take() {
+            Some(clips) => {
+                self.clips = clips;
+                self.tessellate_clipped(&[corners.to_vec()], FillRule::NonZero)
+            }
+            None => vec![
+                [corners[0], corners[1], corners[2]],
+                [corners[0], corners[2], corners[3]],
+            ],
+        };
+
+        let vertices = triangles
+            .iter()
+            .flatten()
+            .map(|corner| {
+                let corner = vec2(corner.x as f32, corner.y as f32);
+                let uv = (to_ndc.project_point3(corner.extend(0.0)).truncate() + 1.0) / 2.0;
+
+                Vertex::new(corner.x, corner.y, 0.0, uv.x, uv.y, color)
+            })
+            .collect::<Vec<Vertex>>();
+
+        let material = match dilation_radius {
+            Some(dilation_radius) => {
+                let material = self.get_material(Shading::Dilate, layer.blend_mode);
+                let texture_size = layer.render_target.texture.size();
+
+                material.set_uniform("TexelSize", vec2(1.0, 1.0) / texture_size);
+                material.set_uniform("DilationRadius", dilation_radius.min(MAX_DILATION_RADIUS));
+
+                material
+            }
+            None => self.get_material(Shading::Layer, layer.blend_mode),
+        };
+
+        gl_use_material(material);
+
+        draw_mesh(&Mesh {
+            indices: (0..vertices.len() as u16).collect::<Vec<u16>>(),
+            vertices,
+            texture: Some(layer.render_target.texture.clone()),
+        });
+
+        gl_use_default_material();
+
+        self.free_render_targets.push(layer.render_target);
+    }
+
+    /// Returns how many pixels `length` local units span on average.
+    fn get_pixel_length(&self, length: f64) -> f32 {
+        let gl = unsafe { get_internal_gl() };
+        let to_ndc = gl.quad_gl.get_projection_matrix() * affine_to_mat4(self.get_transform());
+        let (width, height) = get_target_size();
+        let to_pixels = vec2(width as f32, height as f32) / 2.0;
+
+        let x = (to_ndc.transform_vector3(Vec3::X).truncate() * to_pixels).length();
+        let y = (to_ndc.transform_vector3(Vec3::Y).truncate() * to_pixels).length();
+
+        (x + y) / 2.0 * length as f32
+    }
+
+    fn get_material(&mut self, shading: Shading, blend_mode: BlendMode) -> &Material {
+        self.materials
+            .entry((shading, blend_mode))
+            .or_insert_with(|| load_paint_material(shading, blend_mode))
+    }
+
+    /// Calls `draw` with the material for the blend mode and, if given, the
+    /// gradient set up around it. Drawing solid colors normally outside of
+    /// layers needs no material, while layers need the premultiplied colors
+    /// every material draws.
+    fn with_material(&mut self, gradient: Option<&Gradient>, draw: impl FnOnce(&Self)) {
+        if gradient.is_none() && self.blend_mode == BlendMode::Normal && self.layers.is_empty() {
+            draw(self);
+
+            return;
+        }
+
+        let shading = match gradient {
+            Some(_) => Shading::Gradient,
+            None => Shading::Solid,
+        };
+
+        let material = self.get_material(shading, self.blend_mode);
+
+        if let Some(gradient) = gradient {
+            set_gradient_uniforms(material, gradient);
+        }
+
+        gl_use_material(material);
+        draw(self);
+        gl_use_default_material();
+    }
+
+    /// Calls `draw` with the vertex color to draw with, setting up the
+    /// material for `paint` around it.
+    fn with_paint(&mut self, paint: &Paint, draw: impl FnOnce(&Self, Color)) {
+        match paint {
+            Paint::Solid(color) => {
+                self.with_material(None, |renderer| draw(renderer, srgba_to_color(*color)))
+            }
+            Paint::Gradient(gradient) => {
+                self.with_material(Some(gradient), |renderer| draw(renderer, WHITE))
+            }
+        }
+    }
+
+    fn fill_contours(
+        &mut self,
+        contours: &[Vec<::glam::DVec2>],
+        fill_rule: FillRule,
+        paint: &Paint,
+    ) {
+        let triangles = self.tessellate_clipped(contours, fill_rule);
+
+        self.with_paint(paint, |_, color| {
+            let vertices = triangles
+                .iter()
+                .flatten()
+                .map(|point| Vertex::new(point.x as f32, point.y as f32, 0.0, 0.0, 0.0, color))
+                .collect::<Vec<Vertex>>();
+
+            draw_triangles(&vertices, None);
+        });
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn render_line(
+        &mut self,
+        fonts: &[ChainFont],
+        font_metrics: &FontMetrics,
+        text: &str,
+        position: ::glam::DVec2,
+        anchor: Anchor2D,
+        size: f64,
+        style: &TextStyle,
+        color: Srgba,
+    ) {
+        let (glyphs, width) = get_line_glyphs(fonts, text, size, style.letter_spacing);
+        let origin = get_line_origin(font_metrics, position, anchor, style.anchoring, width);
+
+        self.with_clip_mask(|renderer| {
+            renderer.with_paint(&color.into(), |_, color| {
+                draw_line_glyphs(fonts, &glyphs, origin, size, color);
+            });
+        });
+    }
+
+    /// Draws a line of text over its outline. The outline is stroked along
+    /// the glyph outlines of the fonts whose data is known and grown from the
+    /// drawn glyphs of the rest, by at most [`MAX_DILATION_RADIUS`] pixels.
+    #[allow(clippy::too_many_arguments)]
+    fn render_line_outline(
+        &mut self,
+        fonts: &[ChainFont],
+        font_metrics: &FontMetrics,
+        text: &str,
+        position: ::glam::DVec2,
+        anchor: Anchor2D,
+        size: f64,
+        style: &TextStyle,
+        outline_thickness: f64,
+        color: Srgba,
+        outline_color: Srgba,
+    ) {
+        let (glyphs, width) = get_line_glyphs(fonts, text, size, style.letter_spacing);
+        let origin = get_line_origin(font_metrics, position, anchor, style.anchoring, width);
+
+        let mut path = Path::new();
+
+        for glyph in &glyphs {
+            if let Some(font_data) = &fonts[glyph.font_index].font_data {
+                push_glyph_outline(
+                    &mut path,
+                    font_data,
+                    font_data.glyph_id(glyph.c),
+                    origin + ::glam::DVec2::X * glyph.x,
+                    get_font_scale(font_data, size),
+                );
+            }
+        }
+
+        let unoutlined_glyphs = glyphs
+            .iter()
+            .copied()
+            .filter(|glyph| fonts[glyph.font_index].font_data.is_none())
+            .collect::<Vec<LineGlyph>>();
+
+        // Every piece of a stroke is convex, so the pieces are drawn as fans
+        // into a layer where they overlap without showing their seams.
+        let tolerance = self.get_flattening_tolerance();
+        let outline_vertices = path
+            .flatten(tolerance)
+            .iter()
+            .flat_map(|subpath| {
+                stroke_closed(
+                    &subpath.points,
+                    outline_thickness * 2.0,
+                    LineJoin::Round,
+                    tolerance,
+                )
+            })
+            .flat_map(|piece| {
+                (1..piece.len().saturating_sub(1))
+                    .flat_map(|i| [piece[0], piece[i], piece[i + 1]])
+                    .collect::<Vec<::glam::DVec2>>()
+            })
+            .map(|point| Vertex::new(point.x as f32, point.y as f32, 0.0, 0.0, 0.0, WHITE))
+            .collect::<Vec<Vertex>>();
+
+        let dilation_radius = self.get_pixel_length(outline_thickness);
+
+        self.with_masked_layer(|renderer| {
+            renderer.with_material(None, |_| draw_triangles(&outline_vertices, None));
+
+            if !unoutlined_glyphs.is_empty() {
+                renderer.begin_layer(1.0, BlendMode::Normal);
+                renderer.with_material(None, |_| {
+                    draw_line_glyphs(fonts, &unoutlined_glyphs, origin, size, WHITE);
+                });
+                renderer.end_layer_with(WHITE, Some(dilation_radius));
+            }
+        });
+
+        self.end_layer_with(srgba_to_color(outline_color), None);
+
+        self.with_clip_mask(|renderer| {
+            renderer.with_paint(&color.into(), |_, color| {
+                draw_line_glyphs(fonts, &glyphs, origin, size, color);
+            });
+        });
+    }
 }
 
-impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
-    fn render_point(&mut self, position: ::glam::DVec2, color: Srgba) {
-        draw_rectangle(
-            position.x as f32,
-            position.y as f32,
-            1.0,
-            1.0,
-            srgba_to_color(color),
+/// A font of a fallback chain, with the parsed data it is measured with when
+/// it is known.
+#[derive(Clone)]
+struct ChainFont {
+    font: Option<Font>,
+    font_data: Option<FontArc>,
+}
+
+impl ChainFont {
+    fn from_registry(font_registry: &MacroquadFontRegistry, font_name: &str) -> Self {
+        Self {
+            font: font_registry.get_font(font_name).cloned(),
+            font_data: font_registry.get_font_data(font_name).cloned(),
+        }
+    }
+
+    /// Returns whether the font has a glyph for `c`. Fonts without data are
+    /// taken to have every glyph.
+    fn has_glyph(&self, c: char) -> bool {
+        self.font_data
+            .as_ref()
+            .is_none_or(|font_data| font_data.glyph_id(c).0 != 0)
+    }
+
+    /// Returns the metrics of the font at `size`.
+    fn get_metrics(&self, size: f64) -> FontMetrics {
+        match &self.font_data {
+            Some(font_data) => FontMetrics::from_font(font_data, get_font_scale(font_data, size)),
+            None => DEFAULT_FONT_METRICS.scaled(get_font_size(size)),
+        }
+    }
+
+    /// Returns the decoration metrics of the font at `size`.
+    fn get_decoration_metrics(&self, size: f64) -> DecorationMetrics {
+        match &self.font_data {
+            Some(font_data) => DecorationMetrics::from_font(
+                font_data,
+                &self.get_metrics(size),
+                get_font_scale(font_data, size),
+            ),
+            None => DEFAULT_DECORATION_METRICS.scaled(get_font_size(size)),
+        }
+    }
+}
+
+/// Returns the size macroquad draws text of `size` at, as it only draws text
+/// at whole font sizes.
+fn get_font_size(size: f64) -> f64 {
+    (size as u16) as f64
+}
+
+/// Returns how many local units a font unit of `font_data` is at `size`, with
+/// an em as tall as the size.
+fn get_font_scale(font_data: &FontArc, size: f64) -> f64 {
+    let units_per_em = font_data
+        .units_per_em()
+        .unwrap_or(font_data.height_unscaled());
+
+    get_font_size(size) / units_per_em as f64
+}
+
+/// Splits `text` into runs drawn with the same font of `fonts`, each with the
+/// index of its font.
+fn get_line_runs<'a>(fonts: &[ChainFont], text: &'a str) -> Vec<(usize, &'a str)> {
+    split_by_font(text, fonts.len(), |font_index, c| {
+        fonts[font_index].has_glyph(c)
+    })
+}
+
+/// A glyph of a laid out line of text, with `x` from the start of the line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct LineGlyph {
+    font_index: usize,
+    c: char,
+    x: f64,
+}
+
+/// Lays out the glyphs of a line of text, advancing by the kerned glyph
+/// metrics of the fonts whose data is known and by the measured width of the
+/// glyphs of the others, with `letter_spacing` between glyphs. Returns the
+/// glyphs with the advance of the line.
+fn get_line_glyphs(
+    fonts: &[ChainFont],
+    text: &str,
+    size: f64,
+    letter_spacing: f64,
+) -> (Vec<LineGlyph>, f64) {
+    let mut glyphs = Vec::new();
+    let mut x = 0.0;
+
+    for (font_index, run) in get_line_runs(fonts, text) {
+        let font = &fonts[font_index];
+        let mut previous_id = None;
+
+        for c in run.chars() {
+            let advance = match &font.font_data {
+                Some(font_data) => {
+                    let font_scale = get_font_scale(font_data, size);
+                    let id = font_data.glyph_id(c);
+
+                    if let Some(previous_id) = previous_id {
+                        x += font_data.kern_unscaled(previous_id, id) as f64 * font_scale;
+                    }
+
+                    previous_id = Some(id);
+
+                    font_data.h_advance_unscaled(id) as f64 * font_scale
+                }
+                None => {
+                    measure_text(
+                        c.encode_utf8(&mut [0; 4]),
+                        font.font.as_ref(),
+                        size as u16,
+                        1.0,
+                    )
+                    .width as f64
+                }
+            };
+
+            glyphs.push(LineGlyph { font_index, c, x });
+
+            x += advance + letter_spacing;
+        }
+    }
+
+    let advance = if glyphs.is_empty() {
+        0.0
+    } else {
+        x - letter_spacing
+    };
+
+    (glyphs, advance)
+}
+
+fn get_line_width(fonts: &[ChainFont], text: &str, size: f64, letter_spacing: f64) -> f64 {
+    get_line_glyphs(fonts, text, size, letter_spacing).1
+}
+
+/// Returns where each line of `text` starts on its baseline and how wide it
+/// is.
+fn get_line_starts(
+    fonts: &[ChainFont],
+    font_metrics: &FontMetrics,
+    text: &str,
+    position: ::glam::DVec2,
+    anchor: Anchor2D,
+    size: f64,
+    style: &TextStyle,
+) -> Vec<(::glam::DVec2, f64)> {
+    text.split("\n")
+        .enumerate()
+        .map(|(i, line)| {
+            let width = get_line_width(fonts, line, size, style.letter_spacing);
+
+            (
+                get_line_origin(
+                    font_metrics,
+                    position + ::glam::DVec2::Y * size * i as f64,
+                    anchor,
+                    style.anchoring,
+                    width,
+                ),
+                width,
+            )
+        })
+        .collect::<Vec<(::glam::DVec2, f64)>>()
+}
+
+/// Returns the metrics of the first of `fonts` merged with those of every
+/// other font `text` is drawn with.
+fn get_text_font_metrics(fonts: &[ChainFont], text: &str, size: f64) -> FontMetrics {
+    text.split("\n")
+        .flat_map(|line| get_line_runs(fonts, line))
+        .map(|(font_index, _)| font_index)
+        .unique()
+        .fold(fonts[0].get_metrics(size), |font_metrics, font_index| {
+            font_metrics.merged(&fonts[font_index].get_metrics(size))
+        })
+}
+
+/// Draws laid out glyphs from `origin`, with `origin.y` on their baseline.
+fn draw_line_glyphs(
+    fonts: &[ChainFont],
+    glyphs: &[LineGlyph],
+    origin: ::glam::DVec2,
+    size: f64,
+    color: Color,
+) {
+    for glyph in glyphs {
+        draw_text_ex(
+            glyph.c.encode_utf8(&mut [0; 4]),
+            (origin.x + glyph.x) as f32,
+            origin.y as f32,
+            TextParams {
+                font: fonts[glyph.font_index].font.as_ref(),
+                font_size: size as u16,
+                color,
+                ..TextParams::default()
+            },
         );
     }
+}
+
+/// Returns where a line of text `width` wide is drawn from, with `y` on its
+/// baseline.
+fn get_line_origin(
+    font_metrics: &FontMetrics,
+    position: ::glam::DVec2,
+    anchor: Anchor2D,
+    anchoring: TextAnchoring,
+    width: f64,
+) -> ::glam::DVec2 {
+    let x = match anchor.get_horizontal() {
+        HorizontalAnchor::Left => position.x,
+        HorizontalAnchor::Center => position.x - width / 2.0,
+        HorizontalAnchor::Right => position.x - width,
+    };
+
+    ::glam::dvec2(
+        x,
+        font_metrics.get_baseline(position.y, anchor.get_vertical(), anchoring),
+    )
+}
+
+/// Returns the size in pixels of the screen or render target being drawn to.
+fn get_target_size() -> (u32, u32) {
+    let gl = unsafe { get_internal_gl() };
+
+    match gl.quad_gl.get_active_render_pass() {
+        Some(render_pass) => {
+            let texture = gl.quad_context.render_pass_texture(render_pass);
+
+            gl.quad_context.texture_size(texture)
+        }
+        None => {
+            let (width, height) = miniquad::window::screen_size();
+
+            (width as u32, height as u32)
+        }
+    }
+}
+
+/// Draws a list of triangles, three vertices each, in as few meshes as fit in
+/// a macroquad draw call.
+fn draw_triangles(vertices: &[Vertex], texture: Option<&Texture2D>) {
+    for chunk in vertices.chunks(MAX_MESH_TRIANGLES * 3) {
+        draw_mesh(&Mesh {
+            vertices: chunk.to_vec(),
+            indices: (0..chunk.len() as u16).collect::<Vec<u16>>(),
+            texture: texture.cloned(),
+        });
+    }
+}
+
+impl<R: Borrow<MacroquadImageRegistry>, F: Borrow<MacroquadFontRegistry>> Renderer
+    for MacroquadRenderer<R, F>
+{
+    fn get_blend_mode(&self) -> BlendMode {
+        self.blend_mode
+    }
+
+    fn set_blend_mode(&mut self, blend_mode: BlendMode) {
+        self.blend_mode = blend_mode;
+    }
+
+    fn get_stroke_style(&self) -> &StrokeStyle {
+        &self.stroke_style
+    }
+
+    fn set_stroke_style(&mut self, stroke_style: StrokeStyle) {
+        self.stroke_style = stroke_style;
+    }
+
+    fn get_transform(&self) -> ::glam::DAffine2 {
+        self.transforms
+            .last()
+            .copied()
+            .unwrap_or(::glam::DAffine2::IDENTITY)
+    }
+
+    fn push_transform(&mut self, transform: ::glam::DAffine2) {
+        self.transforms.push(self.get_transform() * transform);
+
+        unsafe { get_internal_gl() }
+            .quad_gl
+            .push_model_matrix(affine_to_mat4(transform));
+    }
+
+    fn pop_transform(&mut self) {
+        if self.transforms.pop().is_some() {
+            unsafe { get_internal_gl() }.quad_gl.pop_model_matrix();
+        }
+    }
+
+    fn push_clip_rect(
+        &mut self,
+        position: ::glam::DVec2,
+        width: f64,
+        height: f64,
+        offset: ::glam::DVec2,
+        rotation: f64,
+    ) {
+        let size = ::glam::dvec2(width, height);
+        let axis = ::glam::DVec2::from_angle(rotation);
+
+        let points = [
+            ::glam::DVec2::ZERO,
+            ::glam::DVec2::X,
+            ::glam::DVec2::ONE,
+            ::glam::DVec2::Y,
+        ]
+        .map(|corner| position + axis.rotate(size * (corner - offset)))
+        .to_vec();
+
+        self.push_clip_contours(&[points], FillRule::NonZero, true);
+    }
+
+    fn push_clip_polygon(&mut self, points: &[::glam::DVec2]) {
+        self.push_clip_contours(&[points.to_vec()], FillRule::EvenOdd, false);
+    }
+
+    fn push_clip_path(&mut self, path: &Path, fill_rule: FillRule) {
+        let contours = path
+            .flatten(self.get_flattening_tolerance())
+            .into_iter()
+            .map(|subpath| subpath.points)
+            .collect::<Vec<Vec<::glam::DVec2>>>();
+
+        self.push_clip_contours(&contours, fill_rule, false);
+    }
+
+    fn pop_clip(&mut self) {
+        if self.clips.pop().is_some() {
+            unsafe { get_internal_gl() }
+                .quad_gl
+                .scissor(self.clips.last().map(|clip| clip.scissor));
+        }
+    }
+
+    fn begin_layer(&mut self, opacity: f32, blend_mode: BlendMode) {
+        let (width, height) = get_target_size();
+
+        let render_target = match self.free_render_targets.pop() {
+            Some(render_target)
+                if render_target.texture.size() == vec2(width as f32, height as f32) =>
+            {
+                render_target
+            }
+            _ => {
+                let render_target = render_target(width, height);
+                render_target.texture.set_filter(FilterMode::Nearest);
+
+                render_target
+            }
+        };
+
+        let layer = Layer {
+            render_target,
+            projection: unsafe { get_internal_gl() }.quad_gl.get_projection_matrix(),
+            opacity,
+            blend_mode,
+            mask_clips: None,
+        };
+
+        push_camera_state();
+        set_camera(&LayerCamera { layer: &layer });
+        clear_background(Color::new(0.0, 0.0, 0.0, 0.0));
+
+        self.layers.push(layer);
+    }
+
+    fn end_layer(&mut self) {
+        self.end_layer_with(WHITE, None);
+    }
+
+    fn render_point(&mut self, position: ::glam::DVec2, color: Srgba) {
+        if self.is_mask_clipped() {
+            self.render_rectangle(position, 1.0, 1.0, ::glam::DVec2::ZERO, 0.0, color.into());
+
+            return;
+        }
+
+        self.with_paint(&color.into(), |_, color| {
+            draw_rectangle(position.x as f32, position.y as f32, 1.0, 1.0, color);
+        });
+    }
 
     fn render_line(
         &mut self,
@@ -64,23 +1124,65 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         thickness: f64,
         color: Srgba,
     ) {
-        draw_line(
-            start.x as f32,
-            start.y as f32,
-            end.x as f32,
-            end.y as f32,
-            thickness as f32,
-            srgba_to_color(color),
-        );
+        if !self.stroke_style.is_solid() || self.is_mask_clipped() {
+            let contours = stroke_with_style(
+                &[start, end],
+                false,
+                thickness,
+                LineJoin::default(),
+                LineCap::Butt,
+                &self.stroke_style,
+                self.get_flattening_tolerance(),
+            );
+
+            self.fill_contours(&contours, FillRule::NonZero, &color.into());
+
+            return;
+        }
+
+        self.with_paint(&color.into(), |_, color| {
+            draw_line(
+                start.x as f32,
+                start.y as f32,
+                end.x as f32,
+                end.y as f32,
+                thickness as f32,
+                color,
+            );
+        });
     }
 
-    fn render_circle(&mut self, position: ::glam::DVec2, radius: f64, color: Srgba) {
-        draw_circle(
-            position.x as f32,
-            position.y as f32,
-            radius as f32,
-            srgba_to_color(color),
+    fn render_polyline(
+        &mut self,
+        points: &[::glam::DVec2],
+        thickness: f64,
+        join: LineJoin,
+        cap: LineCap,
+        color: Srgba,
+    ) {
+        let contours = stroke_with_style(
+            points,
+            false,
+            thickness,
+            join,
+            cap,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_circle(&mut self, position: ::glam::DVec2, radius: f64, paint: Paint) {
+        if self.is_mask_clipped() {
+            self.render_ellipse(position, ::glam::DVec2::splat(radius), 0.0, paint);
+
+            return;
+        }
+
+        self.with_paint(&paint, |_, color| {
+            draw_circle(position.x as f32, position.y as f32, radius as f32, color);
+        });
     }
 
     fn render_circle_lines(
@@ -90,13 +1192,21 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         thickness: f64,
         color: Srgba,
     ) {
-        draw_circle_lines(
-            position.x as f32,
-            position.y as f32,
-            radius as f32,
-            thickness as f32,
-            srgba_to_color(color),
-        );
+        if !self.stroke_style.is_solid() || self.is_mask_clipped() {
+            self.render_arc_lines(position, radius, 0.0, 0, TAU, thickness, color);
+
+            return;
+        }
+
+        self.with_paint(&color.into(), |_, color| {
+            draw_circle_lines(
+                position.x as f32,
+                position.y as f32,
+                radius as f32,
+                thickness as f32,
+                color,
+            );
+        });
     }
 
     fn render_arc(
@@ -106,41 +1216,187 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         rotation: f64,
         sides: u8,
         arc: f64,
-        color: Srgba,
+        paint: Paint,
+    ) {
+        if self.is_mask_clipped() {
+            let contours = get_arc_lines_contours(
+                position,
+                radius + 1.0,
+                rotation,
+                sides,
+                arc,
+                1.0,
+                &StrokeStyle::solid(),
+                self.get_flattening_tolerance(),
+            );
+
+            self.fill_contours(&contours, FillRule::NonZero, &paint);
+
+            return;
+        }
+
+        self.with_paint(&paint, |_, color| {
+            // TODO: yeeeaaah... this is just the outline
+            draw_arc(
+                position.x as f32,
+                position.y as f32,
+                sides,
+                radius as f32,
+                rotation.to_degrees() as f32,
+                1.0,
+                arc.to_degrees() as f32,
+                color,
+            );
+        });
+    }
+
+    fn render_arc_lines(
+        &mut self,
+        position: ::glam::DVec2,
+        radius: f64,
+        rotation: f64,
+        sides: u8,
+        arc: f64,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        if !self.stroke_style.is_solid() || self.is_mask_clipped() {
+            // Macroquad draws arc outlines outside of the radius.
+            let contours = get_arc_lines_contours(
+                position,
+                radius + thickness,
+                rotation,
+                sides,
+                arc,
+                thickness,
+                &self.stroke_style,
+                self.get_flattening_tolerance(),
+            );
+
+            self.fill_contours(&contours, FillRule::NonZero, &color.into());
+
+            return;
+        }
+
+        self.with_paint(&color.into(), |_, color| {
+            draw_arc(
+                position.x as f32,
+                position.y as f32,
+                sides,
+                radius as f32,
+                rotation.to_degrees() as f32,
+                thickness as f32,
+                arc.to_degrees() as f32,
+                color,
+            );
+        });
+    }
+
+    fn render_ellipse(
+        &mut self,
+        position: ::glam::DVec2,
+        radii: ::glam::DVec2,
+        rotation: f64,
+        paint: Paint,
+    ) {
+        self.render_elliptical_arc(position, radii, rotation, 0.0, TAU, paint);
+    }
+
+    fn render_ellipse_lines(
+        &mut self,
+        position: ::glam::DVec2,
+        radii: ::glam::DVec2,
+        rotation: f64,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let mut points = get_elliptical_arc_points(
+            position,
+            radii,
+            rotation,
+            0.0,
+            TAU,
+            self.get_flattening_tolerance(),
+        );
+        points.pop();
+
+        let contours = stroke_with_style(
+            &points,
+            true,
+            thickness,
+            LineJoin::default(),
+            LineCap::Butt,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
+        );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_elliptical_arc(
+        &mut self,
+        position: ::glam::DVec2,
+        radii: ::glam::DVec2,
+        rotation: f64,
+        start_angle: f64,
+        arc: f64,
+        paint: Paint,
     ) {
-        // TODO: yeeeaaah... this is just the outline
-        draw_arc(
-            position.x as f32,
-            position.y as f32,
-            sides,
-            radius as f32,
-            rotation.to_degrees() as f32,
-            1.0,
-            arc.to_degrees() as f32,
-            srgba_to_color(color),
-        );
+        let points = once(position)
+            .chain(get_elliptical_arc_points(
+                position,
+                radii,
+                rotation,
+                start_angle,
+                arc,
+                self.get_flattening_tolerance(),
+            ))
+            .collect::<Vec<::glam::DVec2>>();
+
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
     }
 
-    fn render_arc_lines(
+    fn render_elliptical_arc_lines(
         &mut self,
         position: ::glam::DVec2,
-        radius: f64,
+        radii: ::glam::DVec2,
         rotation: f64,
-        sides: u8,
+        start_angle: f64,
         arc: f64,
         thickness: f64,
         color: Srgba,
     ) {
-        draw_arc(
-            position.x as f32,
-            position.y as f32,
-            sides,
-            radius as f32,
-            rotation.to_degrees() as f32,
-            thickness as f32,
-            arc.to_degrees() as f32,
-            srgba_to_color(color),
+        let points = get_elliptical_arc_points(
+            position,
+            radii,
+            rotation,
+            start_angle,
+            arc,
+            self.get_flattening_tolerance(),
+        );
+
+        let contours = stroke_with_style(
+            &points,
+            false,
+            thickness,
+            LineJoin::default(),
+            LineCap::Butt,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn measure_text(&self, text: &str, size: f64, style: &TextStyle) -> TextMetrics {
+        let fonts = self.get_style_fonts(style);
+
+        get_text_font_metrics(&fonts, text, size).get_text_metrics(
+            size,
+            text.split("\n")
+                .map(|line| get_line_width(&fonts, line, size, style.letter_spacing))
+                .collect::<Vec<f64>>(),
+        )
     }
 
     fn render_text(
@@ -149,40 +1405,43 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         position: ::glam::DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         color: Srgba,
     ) {
-        let measurement = measure_text(text, self.font.as_ref(), size as u16, 1.0);
+        if let Some(transform) = style.get_anchored_transform(position) {
+            self.push_transform(transform);
+            self.render_text(text, position, anchor, size, &style.untransformed(), color);
+            self.pop_transform();
 
-        let x = match anchor.get_horizontal() {
-            HorizontalAnchor::Left => position.x,
-            HorizontalAnchor::Center => position.x - measurement.width as f64 / 2.0,
-            HorizontalAnchor::Right => position.x - measurement.width as f64,
-        };
+            return;
+        }
 
-        let vertical_anchor = anchor.get_vertical();
+        let fonts = self.get_style_fonts(style);
+        let font_metrics = get_text_font_metrics(&fonts, text, size);
+        let line_starts =
+            get_line_starts(&fonts, &font_metrics, text, position, anchor, size, style);
 
-        let y = match (vertical_anchor.get_context(), vertical_anchor.get_value()) {
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Bottom) => position.y,
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Bottom) => {
-                position.y + measurement.offset_y as f64
-            }
-            (_, VerticalAnchorValue::Center) => position.y + measurement.offset_y as f64 / 2.0,
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Top) => {
-                position.y + measurement.offset_y as f64
-            }
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Top) => position.y,
-        };
+        render_text_background(self, style, &font_metrics, &line_starts);
 
-        draw_text_ex(
-            text,
-            x as f32,
-            y as f32,
-            TextParams {
-                font: self.font.as_ref(),
-                font_size: size as u16,
-                color: srgba_to_color(color),
-                ..TextParams::default()
-            },
+        for (i, line) in text.split("\n").enumerate() {
+            self.render_line(
+                &fonts,
+                &font_metrics,
+                line,
+                position + ::glam::DVec2::Y * size * i as f64,
+                anchor,
+                size,
+                style,
+                color,
+            );
+        }
+
+        render_text_decorations(
+            self,
+            style,
+            &fonts[0].get_decoration_metrics(size),
+            &line_starts,
+            color,
         );
     }
 
@@ -192,60 +1451,56 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         position: ::glam::DVec2,
         anchor: Anchor2D,
         size: f64,
+        style: &TextStyle,
         outline_thickness: f64,
         color: Srgba,
         outline_color: Srgba,
     ) {
-        let measurement = measure_text(text, self.font.as_ref(), size as u16, 1.0);
+        if let Some(transform) = style.get_anchored_transform(position) {
+            self.push_transform(transform);
+            self.render_text_outline(
+                text,
+                position,
+                anchor,
+                size,
+                &style.untransformed(),
+                outline_thickness,
+                color,
+                outline_color,
+            );
+            self.pop_transform();
 
-        let x = match anchor.get_horizontal() {
-            HorizontalAnchor::Left => position.x,
-            HorizontalAnchor::Center => position.x - measurement.width as f64 / 2.0,
-            HorizontalAnchor::Right => position.x - measurement.width as f64,
-        };
+            return;
+        }
 
-        let vertical_anchor = anchor.get_vertical();
+        let fonts = self.get_style_fonts(style);
+        let font_metrics = get_text_font_metrics(&fonts, text, size);
+        let line_starts =
+            get_line_starts(&fonts, &font_metrics, text, position, anchor, size, style);
 
-        let y = match (vertical_anchor.get_context(), vertical_anchor.get_value()) {
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Bottom) => position.y,
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Bottom) => {
-                position.y + measurement.offset_y as f64
-            }
-            (_, VerticalAnchorValue::Center) => position.y + measurement.offset_y as f64 / 2.0,
-            (VerticalAnchorContext::Graphics, VerticalAnchorValue::Top) => {
-                position.y + measurement.offset_y as f64
-            }
-            (VerticalAnchorContext::Math, VerticalAnchorValue::Top) => position.y,
-        };
+        render_text_background(self, style, &font_metrics, &line_starts);
 
-        for i in -1..=1 {
-            for j in -1..=1 {
-                if i != 0 || j != 0 {
-                    draw_text_ex(
-                        text,
-                        x as f32 - i as f32 * outline_thickness as f32,
-                        y as f32 - j as f32 * outline_thickness as f32,
-                        TextParams {
-                            font: self.font.as_ref(),
-                            font_size: size as u16,
-                            color: srgba_to_color(outline_color),
-                            ..TextParams::default()
-                        },
-                    );
-                }
-            }
+        for (i, line) in text.split("\n").enumerate() {
+            self.render_line_outline(
+                &fonts,
+                &font_metrics,
+                line,
+                position + ::glam::DVec2::Y * size * i as f64,
+                anchor,
+                size,
+                style,
+                outline_thickness,
+                color,
+                outline_color,
+            );
         }
 
-        draw_text_ex(
-            text,
-            x as f32,
-            y as f32,
-            TextParams {
-                font: self.font.as_ref(),
-                font_size: size as u16,
-                color: srgba_to_color(color),
-                ..TextParams::default()
-            },
+        render_text_decorations(
+            self,
+            style,
+            &fonts[0].get_decoration_metrics(size),
+            &line_starts,
+            color,
         );
     }
 
@@ -256,43 +1511,127 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         height: f64,
         offset: ::glam::DVec2,
         rotation: f64,
+        paint: Paint,
+    ) {
+        if self.is_mask_clipped() {
+            self.render_rounded_rectangle(
+                position,
+                width,
+                height,
+                offset,
+                rotation,
+                CornerRadii::uniform(0.0),
+                paint,
+            );
+
+            return;
+        }
+
+        self.with_paint(&paint, |_, color| {
+            draw_rectangle_ex(
+                position.x as f32,
+                position.y as f32,
+                width as f32,
+                height as f32,
+                DrawRectangleParams {
+                    offset: vec2(offset.x as f32, offset.y as f32),
+                    rotation: rotation as f32,
+                    color,
+                },
+            );
+        });
+    }
+
+    fn render_rectangle_lines(
+        &mut self,
+        position: ::glam::DVec2,
+        width: f64,
+        height: f64,
+        offset: ::glam::DVec2,
+        rotation: f64,
+        thickness: f64,
         color: Srgba,
     ) {
-        draw_rectangle_ex(
-            position.x as f32,
-            position.y as f32,
-            width as f32,
-            height as f32,
-            DrawRectangleParams {
-                offset: vec2(offset.x as f32, offset.y as f32),
-                rotation: rotation as f32,
-                color: srgba_to_color(color),
-            },
+        if !self.stroke_style.is_solid() || self.is_mask_clipped() {
+            self.render_rounded_rectangle_lines(
+                position,
+                width,
+                height,
+                offset,
+                rotation,
+                CornerRadii::uniform(0.0),
+                thickness,
+                color,
+            );
+
+            return;
+        }
+
+        self.with_paint(&color.into(), |_, color| {
+            draw_rectangle_lines_ex(
+                position.x as f32,
+                position.y as f32,
+                width as f32,
+                height as f32,
+                thickness as f32,
+                DrawRectangleParams {
+                    offset: vec2(offset.x as f32, offset.y as f32),
+                    rotation: rotation as f32,
+                    color,
+                },
+            );
+        });
+    }
+
+    fn render_rounded_rectangle(
+        &mut self,
+        position: ::glam::DVec2,
+        width: f64,
+        height: f64,
+        offset: ::glam::DVec2,
+        rotation: f64,
+        radii: CornerRadii,
+        paint: Paint,
+    ) {
+        let size = ::glam::dvec2(width, height);
+
+        let points = get_rounded_rectangle_points(
+            position,
+            position - size * offset,
+            size,
+            rotation,
+            radii,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
     }
 
-    fn render_rectangle_lines(
+    fn render_rounded_rectangle_lines(
         &mut self,
         position: ::glam::DVec2,
         width: f64,
         height: f64,
         offset: ::glam::DVec2,
         rotation: f64,
+        radii: CornerRadii,
         thickness: f64,
         color: Srgba,
     ) {
-        draw_rectangle_lines_ex(
-            position.x as f32,
-            position.y as f32,
-            width as f32,
-            height as f32,
-            thickness as f32,
-            DrawRectangleParams {
-                offset: vec2(offset.x as f32, offset.y as f32),
-                rotation: rotation as f32,
-                color: srgba_to_color(color),
-            },
+        let size = ::glam::dvec2(width, height);
+
+        let contours = get_rounded_rectangle_lines_contours(
+            position,
+            position - size * offset,
+            size,
+            rotation,
+            radii,
+            thickness,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
     }
 
     fn render_equilateral_triangle(
@@ -300,16 +1639,24 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         position: ::glam::DVec2,
         radius: f64,
         rotation: f64,
-        color: Srgba,
+        paint: Paint,
     ) {
-        draw_poly(
-            position.x as f32,
-            position.y as f32,
-            3,
-            radius as f32,
-            rotation.to_degrees() as f32,
-            srgba_to_color(color),
-        );
+        if self.is_mask_clipped() {
+            self.render_regular_polygon(position, radius, rotation, 3, paint);
+
+            return;
+        }
+
+        self.with_paint(&paint, |_, color| {
+            draw_poly(
+                position.x as f32,
+                position.y as f32,
+                3,
+                radius as f32,
+                rotation.to_degrees() as f32,
+                color,
+            );
+        });
     }
 
     fn render_equilateral_triangle_lines(
@@ -320,15 +1667,179 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         thickness: f64,
         color: Srgba,
     ) {
-        draw_poly_lines(
-            position.x as f32,
-            position.y as f32,
-            3,
-            radius as f32,
-            rotation.to_degrees() as f32,
-            thickness as f32,
-            srgba_to_color(color),
+        if !self.stroke_style.is_solid() || self.is_mask_clipped() {
+            // Macroquad draws polygon outlines outside of the radius, spanning
+            // `thickness` along the radius and half of it across the edges.
+            let points = get_regular_polygon_points(position, radius + thickness, rotation, 3);
+            let contours = get_inset_lines_contours(
+                position,
+                &points,
+                thickness / 2.0,
+                &self.stroke_style,
+                self.get_flattening_tolerance(),
+            );
+
+            self.fill_contours(&contours, FillRule::NonZero, &color.into());
+
+            return;
+        }
+
+        self.with_paint(&color.into(), |_, color| {
+            draw_poly_lines(
+                position.x as f32,
+                position.y as f32,
+                3,
+                radius as f32,
+                rotation.to_degrees() as f32,
+                thickness as f32,
+                color,
+            );
+        });
+    }
+
+    fn render_polygon(
+        &mut self,
+        points: &[::glam::DVec2],
+        holes: &[Vec<::glam::DVec2>],
+        paint: Paint,
+    ) {
+        let contours = once(points.to_vec())
+            .chain(holes.iter().cloned())
+            .collect::<Vec<Vec<::glam::DVec2>>>();
+
+        self.fill_contours(&contours, FillRule::EvenOdd, &paint);
+    }
+
+    fn render_polygon_lines(
+        &mut self,
+        points: &[::glam::DVec2],
+        holes: &[Vec<::glam::DVec2>],
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let contours = once(points)
+            .chain(holes.iter().map(Vec::as_slice))
+            .flat_map(|contour| {
+                stroke_with_style(
+                    contour,
+                    true,
+                    thickness,
+                    LineJoin::default(),
+                    LineCap::Butt,
+                    &self.stroke_style,
+                    self.get_flattening_tolerance(),
+                )
+            })
+            .collect::<Vec<Vec<::glam::DVec2>>>();
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_path_fill(&mut self, path: &Path, fill_rule: FillRule, paint: Paint) {
+        let contours = path
+            .flatten(self.get_flattening_tolerance())
+            .into_iter()
+            .map(|subpath| subpath.points)
+            .collect::<Vec<Vec<::glam::DVec2>>>();
+
+        self.fill_contours(&contours, fill_rule, &paint);
+    }
+
+    fn render_path_stroke(
+        &mut self,
+        path: &Path,
+        thickness: f64,
+        join: LineJoin,
+        cap: LineCap,
+        color: Srgba,
+    ) {
+        let contours = path
+            .flatten(self.get_flattening_tolerance())
+            .into_iter()
+            .flat_map(|subpath| {
+                stroke_with_style(
+                    &subpath.points,
+                    subpath.closed,
+                    thickness,
+                    join,
+                    cap,
+                    &self.stroke_style,
+                    self.get_flattening_tolerance(),
+                )
+            })
+            .collect::<Vec<Vec<::glam::DVec2>>>();
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_regular_polygon(
+        &mut self,
+        position: ::glam::DVec2,
+        radius: f64,
+        rotation: f64,
+        sides: u8,
+        paint: Paint,
+    ) {
+        let points = get_regular_polygon_points(position, radius, rotation, sides);
+
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
+    }
+
+    fn render_regular_polygon_lines(
+        &mut self,
+        position: ::glam::DVec2,
+        radius: f64,
+        rotation: f64,
+        sides: u8,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let points = get_regular_polygon_points(position, radius, rotation, sides);
+        let contours = get_inset_lines_contours(
+            position,
+            &points,
+            thickness,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
+        );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
+    }
+
+    fn render_star(
+        &mut self,
+        position: ::glam::DVec2,
+        outer_radius: f64,
+        inner_radius: f64,
+        rotation: f64,
+        points: u8,
+        paint: Paint,
+    ) {
+        let points = get_star_points(position, outer_radius, inner_radius, rotation, points);
+
+        self.fill_contours(&[points], FillRule::NonZero, &paint);
+    }
+
+    fn render_star_lines(
+        &mut self,
+        position: ::glam::DVec2,
+        outer_radius: f64,
+        inner_radius: f64,
+        rotation: f64,
+        points: u8,
+        thickness: f64,
+        color: Srgba,
+    ) {
+        let points = get_star_points(position, outer_radius, inner_radius, rotation, points);
+        let contours = get_inset_lines_contours(
+            position,
+            &points,
+            thickness,
+            &self.stroke_style,
+            self.get_flattening_tolerance(),
         );
+
+        self.fill_contours(&contours, FillRule::NonZero, &color.into());
     }
 
     fn render_image(
@@ -340,21 +1851,460 @@ impl<R: Borrow<MacroquadImageRegistry>> Renderer for MacroquadRenderer<R> {
         offset: ::glam::DVec2,
         rotation: f64,
     ) {
-        if let Some(image) = self.image_registry.borrow().get_image(image_name) {
-            draw_texture_ex(
-                image,
-                (position.x - width * offset.x) as f32,
-                (position.y - height * offset.y) as f32,
-                WHITE,
-                DrawTextureParams {
-                    dest_size: Some(vec2(width as f32, height as f32)),
-                    source: None,
-                    rotation: rotation as f32,
-                    flip_x: false,
-                    flip_y: false,
-                    pivot: Some(dvec2(position.x, position.y).as_vec2()),
-                },
-            );
+        if self.is_mask_clipped() {
+            let size = ::glam::dvec2(width, height);
+            let axis = ::glam::DVec2::from_angle(rotation);
+
+            let vertices = [
+                ::glam::DVec2::ZERO,
+                ::glam::DVec2::X,
+                ::glam::DVec2::ONE,
+                ::glam::DVec2::Y,
+            ]
+            .map(|uv| {
+                MeshVertex::new(
+                    position + axis.rotate(size * (uv - offset)),
+                    uv,
+                    Srgba::new(1.0, 1.0, 1.0, 1.0),
+                )
+            });
+
+            self.render_mesh(&vertices, &[0, 1, 2, 0, 2, 3], Some(image_name));
+
+            return;
+        }
+
+        self.with_material(None, |renderer| {
+            if let Some(image) = renderer.image_registry.borrow().get_image(image_name) {
+                draw_texture_ex(
+                    image,
+                    (position.x - width * offset.x) as f32,
+                    (position.y - height * offset.y) as f32,
+                    WHITE,
+                    DrawTextureParams {
+                        dest_size: Some(vec2(width as f32, height as f32)),
+                        source: None,
+                        rotation: rotation as f32,
+                        flip_x: false,
+                        flip_y: false,
+                        pivot: Some(dvec2(position.x, position.y).as_vec2()),
+                    },
+                );
+            }
+        });
+    }
+
+    fn render_mesh(&mut self, vertices: &[MeshVertex], indices: &[u32], image_name: Option<&str>) {
+        let texture = match image_name {
+            Some(image_name) => match self.image_registry.borrow().get_image(image_name) {
+                Some(texture) => Some(texture.clone()),
+                None => return,
+            },
+            None => None,
+        };
+
+        let triangle_vertices = indices
+            .chunks_exact(3)
+            .filter_map(|triangle| {
+                Some([
+                    *vertices.get(triangle[0] as usize)?,
+                    *vertices.get(triangle[1] as usize)?,
+                    *vertices.get(triangle[2] as usize)?,
+                ])
+            })
+            .flat_map(|triangle| self.clip_mesh_triangle(triangle))
+            .map(|vertex| {
+                Vertex::new(
+                    vertex.position.x as f32,
+                    vertex.position.y as f32,
+                    0.0,
+                    vertex.uv.x as f32,
+                    vertex.uv.y as f32,
+                    srgba_to_color(vertex.color),
+                )
+            })
+            .collect::<Vec<Vertex>>();
+
+        self.with_material(None, |_| {
+            draw_triangles(&triangle_vertices, texture.as_ref())
+        });
+    }
+}
+
+const MAX_GRADIENT_STOPS: usize = 16;
+
+const PAINT_VERTEX_SHADER: &str = r#"#version 100
+attribute vec3 position;
+attribute vec2 texcoord;
+attribute vec4 color0;
+
+varying lowp vec4 color;
+varying lowp vec2 uv;
+varying highp vec2 local_position;
+varying highp vec2 screen_uv;
+
+uniform mat4 Model;
+uniform mat4 Projection;
+
+void main() {
+    gl_Position = Projection * Model * vec4(position, 1);
+    color = color0 / 255.0;
+    uv = texcoord;
+    local_position = position.xy;
+    screen_uv = (gl_Position.xy / gl_Position.w + 1.0) / 2.0;
+}
+"#;
+
+const PAINT_FRAGMENT_SHADER_HEADER: &str = r#"#version 100
+#ifdef GL_FRAGMENT_PRECISION_HIGH
+precision highp float;
+#else
+precision mediump float;
+#endif
+
+varying lowp vec4 color;
+varying lowp vec2 uv;
+varying vec2 local_position;
+varying vec2 screen_uv;
+
+uniform sampler2D Texture;
+"#;
+
+const GRADIENT_FRAGMENT_SHADER_FUNCTIONS: &str = r#"
+uniform int GradientKind;
+uniform vec4 GradientGeometry;
+uniform int SpreadMode;
+uniform int ColorSpace;
+uniform int StopCount;
+uniform vec4 StopColors[16];
+uniform float StopOffsets[16];
+
+const float TAU = 6.283185307179586;
+
+vec3 linear_to_srgb(vec3 linear) {
+    vec3 low = linear * 12.92;
+    vec3 high = 1.055 * pow(max(linear, 0.0), vec3(1.0 / 2.4)) - 0.055;
+    return mix(low, high, step(vec3(0.0031308), linear));
+}
+
+vec3 oklab_to_linear(vec3 oklab) {
+    float l = oklab.x + 0.3963377774 * oklab.y + 0.2158037573 * oklab.z;
+    float m = oklab.x - 0.1055613458 * oklab.y - 0.0638541728 * oklab.z;
+    float s = oklab.x - 0.0894841775 * oklab.y - 1.2914855480 * oklab.z;
+
+    l = l * l * l;
+    m = m * m * m;
+    s = s * s * s;
+
+    return vec3(
+        4.0767416621 * l - 3.3077115913 * m + 0.2309699292 * s,
+        -1.2684380046 * l + 2.6097574011 * m - 0.3413193965 * s,
+        -0.0041960863 * l - 0.7034186147 * m + 1.7076147010 * s
+    );
+}
+
+float get_offset() {
+    if (GradientKind == 0) {
+        vec2 direction = GradientGeometry.zw - GradientGeometry.xy;
+        float length_squared = dot(direction, direction);
+
+        if (length_squared == 0.0) {
+            return 0.0;
+        }
+
+        return dot(local_position - GradientGeometry.xy, direction) / length_squared;
+    } else if (GradientKind == 1) {
+        if (GradientGeometry.z == 0.0) {
+            return 1.0;
+        }
+
+        return length(local_position - GradientGeometry.xy) / GradientGeometry.z;
+    } else {
+        vec2 relative = local_position - GradientGeometry.xy;
+        return mod(atan(relative.y, relative.x) - GradientGeometry.z, TAU) / TAU;
+    }
+}
+
+float apply_spread(float offset) {
+    if (SpreadMode == 0) {
+        return clamp(offset, 0.0, 1.0);
+    } else if (SpreadMode == 1) {
+        return fract(offset);
+    } else {
+        return 1.0 - abs(mod(offset, 2.0) - 1.0);
+    }
+}
+
+vec4 get_gradient_color() {
+    float offset = apply_spread(get_offset());
+    vec4 components = StopColors[0];
+
+    for (int i = 1; i < 16; i++) {
+        if (i >= StopCount) {
+            break;
+        }
+
+        if (offset < StopOffsets[i]) {
+            if (offset >= StopOffsets[i - 1]) {
+                float t = (offset - StopOffsets[i - 1]) / (StopOffsets[i] - StopOffsets[i - 1]);
+                components = mix(StopColors[i - 1], StopColors[i], t);
+            }
+
+            break;
+        }
+
+        components = StopColors[i];
+    }
+
+    if (components.a <= 0.0) {
+        return vec4(0.0);
+    }
+
+    vec3 rgb = components.rgb / components.a;
+
+    if (ColorSpace == 1) {
+        rgb = linear_to_srgb(rgb);
+    } else if (ColorSpace == 2) {
+        rgb = linear_to_srgb(oklab_to_linear(rgb));
+    }
+
+    return vec4(rgb, components.a);
+}
+"#;
+
+/// Returns the fragment shader for drawing with or without a gradient in a
+/// blend mode. The shader outputs premultiplied colors, which blend modes the
+/// blend states cannot express first blend with a copy of the screen.
+fn get_paint_fragment_shader(shading: Shading, blend_mode: BlendMode) -> String {
+    let mut source = PAINT_FRAGMENT_SHADER_HEADER.to_owned();
+
+    match shading {
+        Shading::Gradient => source.push_str(GRADIENT_FRAGMENT_SHADER_FUNCTIONS),
+        Shading::Dilate => source.push_str(&format!(
+            "
+uniform vec2 TexelSize;
+uniform float DilationRadius;
+
+const int MAX_DILATION = {};
+",
+            MAX_DILATION_RADIUS as i32
+        )),
+        Shading::Solid | Shading::Layer => {}
+    }
+
+    let blend = match blend_mode {
+        BlendMode::Multiply => Some("source * destination"),
+        BlendMode::Overlay => Some(
+            "mix(2.0 * source * destination, \
+             1.0 - 2.0 * (1.0 - source) * (1.0 - destination), \
+             step(0.5, destination))",
+        ),
+        BlendMode::Darken => Some("min(source, destination)"),
+        BlendMode::Lighten => Some("max(source, destination)"),
+        _ => None,
+    };
+
+    if let Some(blend) = blend {
+        source.push_str(&format!(
+            "
+uniform sampler2D _ScreenTexture;
+
+vec3 blend(vec3 source, vec3 destination) {{
+    return {blend};
+}}
+"
+        ));
+    }
+
+    source.push_str(
+        "
+void main() {
+    vec4 source = color * texture2D(Texture, uv);
+",
+    );
+
+    match shading {
+        Shading::Solid => {}
+        Shading::Gradient => source.push_str("    source *= get_gradient_color();\n"),
+        Shading::Layer => source.push_str(
+            "    source = texture2D(Texture, uv);
+    source.rgb = source.a > 0.0 ? source.rgb / source.a : vec3(0.0);
+    source *= color;
+",
+        ),
+        Shading::Dilate => source.push_str(
+            "    float coverage = 0.0;
+
+    for (int i = -MAX_DILATION; i <= MAX_DILATION; i++) {
+        for (int j = -MAX_DILATION; j <= MAX_DILATION; j++) {
+            vec2 offset = vec2(float(i), float(j));
+
+            if (length(offset) <= DilationRadius) {
+                coverage = max(coverage, texture2D(Texture, uv + offset * TexelSize).a);
+            }
         }
     }
+
+    source = vec4(color.rgb, color.a * coverage);
+",
+        ),
+    }
+
+    if blend.is_some() {
+        source.push_str(
+            "    vec4 destination = texture2D(_ScreenTexture, screen_uv);
+    source.rgb = mix(source.rgb, blend(source.rgb, destination.rgb), destination.a);
+",
+        );
+    }
+
+    source.push_str(
+        "    gl_FragColor = vec4(source.rgb * source.a, source.a);
+}
+",
+    );
+
+    source
+}
+
+/// Returns the color and alpha blend states that composite the premultiplied
+/// output of the paint shaders in a blend mode.
+fn get_blend_states(blend_mode: BlendMode) -> (BlendState, BlendState) {
+    let source_over = BlendState::new(
+        Equation::Add,
+        BlendFactor::One,
+        BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+    );
+
+    let color_blend = match blend_mode {
+        BlendMode::Additive => BlendState::new(Equation::Add, BlendFactor::One, BlendFactor::One),
+        BlendMode::Screen => BlendState::new(
+            Equation::Add,
+            BlendFactor::One,
+            BlendFactor::OneMinusValue(BlendValue::SourceColor),
+        ),
+        BlendMode::Erase => BlendState::new(
+            Equation::Add,
+            BlendFactor::Zero,
+            BlendFactor::OneMinusValue(BlendValue::SourceAlpha),
+        ),
+        BlendMode::Normal
+        | BlendMode::Multiply
+        | BlendMode::Overlay
+        | BlendMode::Darken
+        | BlendMode::Lighten => source_over,
+    };
+
+    let alpha_blend = match blend_mode {
+        BlendMode::Additive => BlendState::new(Equation::Add, BlendFactor::One, BlendFactor::One),
+        BlendMode::Erase => color_blend,
+        _ => source_over,
+    };
+
+    (color_blend, alpha_blend)
+}
+
+fn load_paint_material(shading: Shading, blend_mode: BlendMode) -> Material {
+    let (color_blend, alpha_blend) = get_blend_states(blend_mode);
+
+    let uniforms = match shading {
+        Shading::Gradient => vec![
+            UniformDesc::new("GradientKind", UniformType::Int1),
+            UniformDesc::new("GradientGeometry", UniformType::Float4),
+            UniformDesc::new("SpreadMode", UniformType::Int1),
+            UniformDesc::new("ColorSpace", UniformType::Int1),
+            UniformDesc::new("StopCount", UniformType::Int1),
+            UniformDesc::new("StopColors", UniformType::Float4).array(MAX_GRADIENT_STOPS),
+            UniformDesc::new("StopOffsets", UniformType::Float1).array(MAX_GRADIENT_STOPS),
+        ],
+        Shading::Dilate => vec![
+            UniformDesc::new("TexelSize", UniformType::Float2),
+            UniformDesc::new("DilationRadius", UniformType::Float1),
+        ],
+        Shading::Solid | Shading::Layer => Vec::new(),
+    };
+
+    load_material(
+        ShaderSource::Glsl {
+            vertex: PAINT_VERTEX_SHADER,
+            fragment: &get_paint_fragment_shader(shading, blend_mode),
+        },
+        MaterialParams {
+            pipeline_params: PipelineParams {
+                color_blend: Some(color_blend),
+                alpha_blend: Some(alpha_blend),
+                ..Default::default()
+            },
+            uniforms,
+            ..Default::default()
+        },
+    )
+    .expect("the paint shader is valid")
+}
+
+/// Removes the inner stops that change the gradient the least until at most
+/// `count` stops remain, keeping the first and last stops.
+fn get_reduced_stops(mut stops: Vec<(f32, [f32; 4])>, count: usize) -> Vec<(f32, [f32; 4])> {
+    while stops.len() > count.max(2) {
+        let (i, _) = stops
+            .iter()
+            .tuple_windows()
+            .map(|(start, stop, end)| {
+                let span = end.0 - start.0;
+                let t = if span > 0.0 {
+                    (stop.0 - start.0) / span
+                } else {
+                    0.5
+                };
+
+                (0..4)
+                    .map(|j| (start.1[j] + (end.1[j] - start.1[j]) * t - stop.1[j]).abs())
+                    .fold(0.0, f32::max)
+            })
+            .enumerate()
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("there are inner stops");
+
+        stops.remove(i + 1);
+    }
+
+    stops
+}
+
+fn set_gradient_uniforms(material: &Material, gradient: &Gradient) {
+    let (kind, geometry) = match gradient.kind {
+        GradientKind::Linear { start, end } => (
+            0,
+            vec4(start.x as f32, start.y as f32, end.x as f32, end.y as f32),
+        ),
+        GradientKind::Radial { center, radius } => (
+            1,
+            vec4(center.x as f32, center.y as f32, radius as f32, 0.0),
+        ),
+        GradientKind::Conic {
+            center,
+            start_angle,
+        } => (
+            2,
+            vec4(center.x as f32, center.y as f32, start_angle as f32, 0.0),
+        ),
+    };
+
+    let stops = get_reduced_stops(gradient.get_interpolation_stops(), MAX_GRADIENT_STOPS);
+
+    let mut stop_colors = [Vec4::ZERO; MAX_GRADIENT_STOPS];
+    let mut stop_offsets = [0.0f32; MAX_GRADIENT_STOPS];
+
+    for (i, (offset, components)) in stops.iter().enumerate() {
+        stop_colors[i] = Vec4::from_array(*components);
+        stop_offsets[i] = *offset;
+    }
+
+    material.set_uniform("GradientKind", kind);
+    material.set_uniform("GradientGeometry", geometry);
+    material.set_uniform("SpreadMode", gradient.spread as i32);
+    material.set_uniform("ColorSpace", gradient.color_space as i32);
+    material.set_uniform("StopCount", stops.len().clamp(1, MAX_GRADIENT_STOPS) as i32);
+    material.set_uniform_array("StopColors", &stop_colors);
+    material.set_uniform_array("StopOffsets", &stop_offsets);
 }
@@ -0,0 +1,191 @@
+use std::str::FromStr;
+
+use anchor2d::{Anchor2D, HorizontalAnchor};
+use glam::{DVec2, dvec2};
+use palette::{Srgb, Srgba};
+
+use crate::{TextMetrics, TextStyle, font_metrics::FontMetrics};
+
+/// A piece of text drawn with its own look, as part of a line of spans.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+    pub text: String,
+    pub color: Srgba,
+    pub size: f64,
+    pub style: TextStyle,
+    pub underline: bool,
+}
+
+impl TextSpan {
+    pub fn new(text: String, color: Srgba, size: f64, style: TextStyle, underline: bool) -> Self {
+        Self {
+            text,
+            color,
+            size,
+            style,
+            underline,
+        }
+    }
+
+    /// Parses `markup` into spans, starting from `color`, `size` and `style`
+    /// without an underline. The tags `[color=#rrggbb]` or `[color=#rrggbbaa]`,
+    /// `[size=N]`, `[font=Name]` and `[u]` change the look of the text up to
+    /// their closing tag, like `[/color]`, and `[[` is a literal `[`. Anything
+    /// else in brackets, including closing tags that do not match the last tag
+    /// opened, is kept as text.
+    pub fn from_markup(markup: &str, color: Srgba, size: f64, style: &TextStyle) -> Vec<Self> {
+        let mut spans = Vec::new();
+        let mut formats = vec![(
+            String::new(),
+            Self::new(String::new(), color, size, style.clone(), false),
+        )];
+        let mut text = String::new();
+        let mut rest = markup;
+
+        while let Some(tag_start) = rest.find('[') {
+            text.push_str(&rest[..tag_start]);
+            rest = &rest[tag_start..];
+
+            if let Some(after) = rest.strip_prefix("[[") {
+                text.push('[');
+                rest = after;
+
+                continue;
+            }
+
+            let Some(tag_end) = rest.find(']') else {
+                break;
+            };
+
+            let tag = &rest[1..tag_end];
+            let (_, format) = formats.last().unwrap();
+
+            let is_applied = match tag.strip_prefix('/') {
+                Some(name) => formats.len() > 1 && formats.last().unwrap().0 == name,
+                None => format.with_tag(tag).is_some(),
+            };
+
+            if is_applied {
+                if !text.is_empty() {
+                    spans.push(Self {
+                        text: std::mem::take(&mut text),
+                        ..format.clone()
+                    });
+                }
+
+                if tag.starts_with('/') {
+                    formats.pop();
+                } else {
+                    let format = format.with_tag(tag).unwrap();
+                    let name = tag.split('=').next().unwrap_or_default().to_string();
+
+                    formats.push((name, format));
+                }
+            } else {
+                text.push_str(&rest[..=tag_end]);
+            }
+
+            rest = &rest[tag_end + 1..];
+        }
+
+        text.push_str(rest);
+
+        if !text.is_empty() {
+            let (_, format) = formats.last().unwrap();
+
+            spans.push(Self {
+                text,
+                ..format.clone()
+            });
+        }
+
+        spans
+    }
+
+    /// Returns the look of the span changed by the opening markup `tag`, or
+    /// `None` when the tag is not known.
+    fn with_tag(&self, tag: &str) -> Option<Self> {
+        let mut span = self.clone();
+
+        match tag.split_once('=') {
+            Some(("color", value)) => {
+                span.color = Srgba::<u8>::from_str(value)
+                    .or_else(|_| Srgb::<u8>::from_str(value).map(|color| color.into()))
+                    .ok()?
+                    .into_format();
+            }
+            Some(("size", value)) => span.size = value.parse::<f64>().ok()?,
+            Some(("font", value)) => span.style.font_name = Some(value.to_string()),
+            None if tag == "u" => span.underline = true,
+            _ => return None,
+        }
+
+        Some(span)
+    }
+}
+
+/// Returns the metrics of spans with the given metrics drawn as one line,
+/// reaching as far above and below the baseline as the tallest of them.
+fn merge_span_metrics(span_metrics: &[TextMetrics]) -> TextMetrics {
+    let font_metrics = span_metrics
+        .iter()
+        .map(FontMetrics::from_text_metrics)
+        .reduce(|a, b| FontMetrics {
+            cap_height: a.cap_height.max(b.cap_height),
+            ..a.merged(&b)
+        })
+        .unwrap_or_default();
+
+    font_metrics.get_text_metrics(
+        0.0,
+        vec![span_metrics.iter().map(|metrics| metrics.width).sum()],
+    )
+}
+
+/// Returns the metrics of `spans` as one line, with `measure` returning the
+/// metrics of a span on its own.
+pub(crate) fn measure_spans(
+    spans: &[TextSpan],
+    measure: impl Fn(&TextSpan) -> TextMetrics,
+) -> TextMetrics {
+    merge_span_metrics(&spans.iter().map(measure).collect::<Vec<TextMetrics>>())
+}
+
+/// Lays out `spans` one after another on a shared baseline, with the line
+/// anchored as a whole at `position` by the anchoring of the first span.
+/// Returns where each span starts on the baseline.
+pub(crate) fn layout_spans(
+    spans: &[TextSpan],
+    position: DVec2,
+    anchor: Anchor2D,
+    measure: impl Fn(&TextSpan) -> TextMetrics,
+) -> Vec<DVec2> {
+    let span_metrics = spans.iter().map(measure).collect::<Vec<TextMetrics>>();
+    let metrics = merge_span_metrics(&span_metrics);
+
+    let anchoring = spans
+        .first()
+        .map(|span| span.style.anchoring)
+        .unwrap_or_default();
+
+    let mut x = match anchor.get_horizontal() {
+        HorizontalAnchor::Left => position.x,
+        HorizontalAnchor::Center => position.x - metrics.width / 2.0,
+        HorizontalAnchor::Right => position.x - metrics.width,
+    };
+    let baseline = FontMetrics::from_text_metrics(&metrics).get_baseline(
+        position.y,
+        anchor.get_vertical(),
+        anchoring,
+    );
+
+    let mut runs = Vec::new();
+
+    for metrics in span_metrics {
+        runs.push(dvec2(x, baseline));
+
+        x += metrics.width;
+    }
+
+    runs
+}
@@ -0,0 +1,215 @@
+//! Glyph layout for lines of text. Lines are split into runs by direction,
+//! script and font, which are shaped with the OpenType features of their
+//! fonts and laid out left to right in visual order.
+
+use ab_glyph::{Font, FontArc, GlyphId, ScaleFont};
+use glam::{DVec2, dvec2};
+use itertools::Itertools;
+use rustybuzz::{Direction, Face, UnicodeBuffer};
+use unicode_bidi::ParagraphBidiInfo;
+use unicode_script::{Script, UnicodeScript};
+
+use crate::font_fallback::split_by_font;
+
+/// A glyph of a shaped line, with `x` in local units from the start of the
+/// line and `y` in local units below the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ShapedGlyph {
+    /// The index of the font of the fallback chain the glyph is drawn with.
+    pub font_index: usize,
+    pub id: GlyphId,
+    pub x: f64,
+    pub y: f64,
+}
+
+/// A line of text as the glyphs it is drawn with.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct ShapedLine {
+    pub glyphs: Vec<ShapedGlyph>,
+    /// How far the caret moves over the line, in local units.
+    pub advance: f64,
+}
+
+impl ShapedLine {
+    /// Returns the indices of the fonts the line is drawn with.
+    pub fn get_font_indices(&self) -> Vec<usize> {
+        self.glyphs
+            .iter()
+            .map(|glyph| glyph.font_index)
+            .unique()
+            .collect::<Vec<usize>>()
+    }
+}
+
+/// A glyph of a shaped run, in font units.
+struct RunGlyph {
+    id: GlyphId,
+    offset: DVec2,
+    advance: f64,
+}
+
+/// Shapes lines of text with a fallback chain of fonts, parsing the layout
+/// tables of each font once for every line it shapes.
+pub(crate) struct Shaper<'a> {
+    pub fonts: &'a [FontArc],
+    faces: Vec<Option<Face<'a>>>,
+}
+
+impl<'a> Shaper<'a> {
+    pub fn new(fonts: &'a [FontArc]) -> Self {
+        Self {
+            fonts,
+            faces: fonts
+                .iter()
+                .map(|font| Face::from_slice(font.font_data(), 0))
+                .collect::<Vec<Option<Face<'a>>>>(),
+        }
+    }
+
+    /// Shapes the line `text` at `size`, drawing each character with the
+    /// first of the fonts that has a glyph for it and adding `letter_spacing`
+    /// after each glyph that moves the caret, except the last.
+    pub fn shape(&self, text: &str, size: f64, letter_spacing: f64) -> ShapedLine {
+        if text.is_empty() {
+            return ShapedLine::default();
+        }
+
+        let bidi_info = ParagraphBidiInfo::new(text, None);
+        let (levels, runs) = bidi_info.visual_runs(0..text.len());
+
+        let mut glyphs = Vec::new();
+        let mut caret = 0.0;
+        let mut is_spaced = false;
+
+        for run in runs {
+            let is_rtl = levels[run.start].is_rtl();
+
+            let mut pieces = split_by_script(&text[run])
+                .into_iter()
+                .flat_map(|piece| {
+                    split_by_font(piece, self.fonts.len(), |font_index, c| {
+                        self.fonts[font_index].glyph_id(c).0 != 0
+                    })
+                })
+                .collect::<Vec<(usize, &str)>>();
+
+            if is_rtl {
+                pieces.reverse();
+            }
+
+            for (font_index, piece) in pieces {
+                let font_scale = get_font_scale(&self.fonts[font_index], size);
+
+                for run_glyph in self.shape_run(font_index, piece, is_rtl) {
+                    glyphs.push(ShapedGlyph {
+                        font_index,
+                        id: run_glyph.id,
+                        x: caret + run_glyph.offset.x * font_scale,
+                        y: run_glyph.offset.y * font_scale,
+                    });
+
+                    caret += run_glyph.advance * font_scale;
+
+                    if run_glyph.advance != 0.0 {
+                        caret += letter_spacing;
+                        is_spaced = true;
+                    }
+                }
+            }
+        }
+
+        ShapedLine {
+            advance: if is_spaced {
+                caret - letter_spacing
+            } else {
+                caret
+            },
+            glyphs,
+        }
+    }
+
+    /// Shapes `text`, which is in one direction and script, with the font at
+    /// `font_index` alone, returning its glyphs in visual order.
+    fn shape_run(&self, font_index: usize, text: &str, is_rtl: bool) -> Vec<RunGlyph> {
+        let Some(face) = &self.faces[font_index] else {
+            let font = &self.fonts[font_index];
+
+            let mut glyphs = text
+                .chars()
+                .map(|c| {
+                    let id = font.glyph_id(c);
+
+                    RunGlyph {
+                        id,
+                        offset: DVec2::ZERO,
+                        advance: font.h_advance_unscaled(id) as f64,
+                    }
+                })
+                .collect::<Vec<RunGlyph>>();
+
+            if is_rtl {
+                glyphs.reverse();
+            }
+
+            return glyphs;
+        };
+
+        let mut buffer = UnicodeBuffer::new();
+        buffer.push_str(text);
+        buffer.set_direction(if is_rtl {
+            Direction::RightToLeft
+        } else {
+            Direction::LeftToRight
+        });
+
+        let glyph_buffer = rustybuzz::shape(face, &[], buffer);
+
+        glyph_buffer
+            .glyph_infos()
+            .iter()
+            .zip(glyph_buffer.glyph_positions())
+            .map(|(info, position)| RunGlyph {
+                id: GlyphId(info.glyph_id as u16),
+                offset: dvec2(position.x_offset as f64, -position.y_offset as f64),
+                advance: position.x_advance as f64,
+            })
+            .collect::<Vec<RunGlyph>>()
+    }
+}
+
+/// Returns how many local units a font unit is at `size`.
+pub(crate) fn get_font_scale(font: &FontArc, size: f64) -> f64 {
+    font.as_scaled(size as f32).h_scale_factor() as f64
+}
+
+/// Splits `text` into runs of one script each. Characters shared between
+/// scripts, like spaces and punctuation, belong to the run before them.
+fn split_by_script(text: &str) -> Vec<&str> {
+    let mut pieces = Vec::new();
+    let mut start = 0;
+    let mut script = None;
+
+    for (i, c) in text.char_indices() {
+        let char_script = c.script();
+
+        if matches!(
+            char_script,
+            Script::Common | Script::Inherited | Script::Unknown
+        ) {
+            continue;
+        }
+
+        if script.is_some_and(|script| script != char_script) {
+            pieces.push(&text[start..i]);
+            start = i;
+        }
+
+        script = Some(char_script);
+    }
+
+    if start < text.len() {
+        pieces.push(&text[start..]);
+    }
+
+    pieces
+}
@@ -0,0 +1,67 @@
+/// How two connected segments of a stroke are joined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineJoin {
+    /// Extends the outer edges until they meet, falling back to a bevel when
+    /// the miter would be longer than the given multiple of the half thickness.
+    Miter(f64),
+    Round,
+    Bevel,
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        Self::Miter(4.0)
+    }
+}
+
+/// How the open ends of a stroke are drawn.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+/// The dash pattern outlines are drawn with.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct StrokeStyle {
+    /// Alternating lengths of dashes and gaps, starting with a dash. A pattern
+    /// with an odd number of lengths is repeated to make it even, and an empty
+    /// pattern draws solid outlines.
+    pub dashes: Vec<f64>,
+    /// How far into the pattern outlines start.
+    pub dash_offset: f64,
+    /// How the ends of every dash are drawn.
+    pub dash_cap: LineCap,
+}
+
+impl StrokeStyle {
+    pub fn new(dashes: Vec<f64>, dash_offset: f64, dash_cap: LineCap) -> Self {
+        Self {
+            dashes,
+            dash_offset,
+            dash_cap,
+        }
+    }
+
+    pub fn solid() -> Self {
+        Self::default()
+    }
+
+    pub fn dashed(dashes: Vec<f64>, dash_offset: f64) -> Self {
+        Self::new(dashes, dash_offset, LineCap::Butt)
+    }
+
+    /// Round dots as wide as the outline, `spacing` apart from center to
+    /// center.
+    pub fn dotted(spacing: f64) -> Self {
+        Self::new(vec![0.0, spacing], 0.0, LineCap::Round)
+    }
+
+    /// Returns whether outlines are drawn without gaps, which is also the case
+    /// for patterns with negative or no total length.
+    pub fn is_solid(&self) -> bool {
+        self.dashes.iter().any(|length| *length < 0.0) || self.dashes.iter().sum::<f64>() <= 0.0
+    }
+}
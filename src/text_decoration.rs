@@ -0,0 +1,161 @@
+use glam::{DVec2, dvec2};
+use palette::Srgba;
+
+use crate::{Renderer, TextStyle, font_metrics::FontMetrics};
+
+/// Where the lines drawn under and through text go at one size, in the
+/// coordinates text is drawn in, with offsets to the top of each line below
+/// the baseline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct DecorationMetrics {
+    pub underline_offset: f64,
+    pub underline_thickness: f64,
+    pub strikethrough_offset: f64,
+    pub strikethrough_thickness: f64,
+}
+
+impl DecorationMetrics {
+    /// Reads the decoration metrics of `font`, with `scale` converting font
+    /// units to the coordinates text is drawn in, or estimates them from
+    /// `font_metrics` where the font does not have them.
+    #[cfg(any(feature = "image", feature = "macroquad"))]
+    pub fn from_font(font: &impl ab_glyph::Font, font_metrics: &FontMetrics, scale: f64) -> Self {
+        let face = ttf_parser::Face::parse(font.font_data(), 0).ok();
+        let estimate = Self::from_font_metrics(font_metrics);
+
+        let (underline_offset, underline_thickness) = face
+            .as_ref()
+            .and_then(|face| face.underline_metrics())
+            .map(|metrics| {
+                (
+                    -metrics.position as f64 * scale,
+                    metrics.thickness as f64 * scale,
+                )
+            })
+            .unwrap_or((estimate.underline_offset, estimate.underline_thickness));
+
+        let (strikethrough_offset, strikethrough_thickness) = face
+            .as_ref()
+            .and_then(|face| face.strikeout_metrics())
+            .map(|metrics| {
+                (
+                    -metrics.position as f64 * scale,
+                    metrics.thickness as f64 * scale,
+                )
+            })
+            .unwrap_or((
+                estimate.strikethrough_offset,
+                estimate.strikethrough_thickness,
+            ));
+
+        Self {
+            underline_offset,
+            underline_thickness,
+            strikethrough_offset,
+            strikethrough_thickness,
+        }
+    }
+
+    /// Estimates decoration metrics from the vertical metrics of a font, with
+    /// the strikethrough around the middle of the lowercase letters.
+    pub fn from_font_metrics(font_metrics: &FontMetrics) -> Self {
+        let thickness = font_metrics.ascent / 16.0;
+
+        Self {
+            underline_offset: font_metrics.descent / 4.0,
+            underline_thickness: thickness,
+            strikethrough_offset: -font_metrics.cap_height * 0.35 - thickness / 2.0,
+            strikethrough_thickness: thickness,
+        }
+    }
+
+    #[cfg(feature = "macroquad")]
+    pub fn scaled(&self, scale: f64) -> Self {
+        Self {
+            underline_offset: self.underline_offset * scale,
+            underline_thickness: self.underline_thickness * scale,
+            strikethrough_offset: self.strikethrough_offset * scale,
+            strikethrough_thickness: self.strikethrough_thickness * scale,
+        }
+    }
+}
+
+/// Draws the background of `style` behind text whose lines start on their
+/// baselines at the given points with the given widths.
+pub(crate) fn render_text_background(
+    renderer: &mut impl Renderer,
+    style: &TextStyle,
+    font_metrics: &FontMetrics,
+    lines: &[(DVec2, f64)],
+) {
+    let Some(background) = style.background else {
+        return;
+    };
+
+    let Some(top) = lines
+        .first()
+        .map(|(start, _)| start.y - font_metrics.ascent)
+    else {
+        return;
+    };
+    let bottom = lines
+        .last()
+        .map(|(start, _)| start.y + font_metrics.descent)
+        .unwrap_or(top);
+
+    let left = lines
+        .iter()
+        .map(|(start, _)| start.x)
+        .fold(f64::INFINITY, f64::min);
+    let right = lines
+        .iter()
+        .map(|(start, width)| start.x + width)
+        .fold(f64::NEG_INFINITY, f64::max);
+
+    renderer.render_rectangle(
+        dvec2(left, top) - background.padding,
+        right - left + background.padding * 2.0,
+        bottom - top + background.padding * 2.0,
+        DVec2::ZERO,
+        0.0,
+        background.color.into(),
+    );
+}
+
+/// Draws the underline and strikethrough of `style` over lines of text that
+/// start on their baselines at the given points with the given widths.
+pub(crate) fn render_text_decorations(
+    renderer: &mut impl Renderer,
+    style: &TextStyle,
+    decoration_metrics: &DecorationMetrics,
+    lines: &[(DVec2, f64)],
+    color: Srgba,
+) {
+    let decorations = [
+        (
+            style.underline,
+            decoration_metrics.underline_offset,
+            decoration_metrics.underline_thickness,
+        ),
+        (
+            style.strikethrough,
+            decoration_metrics.strikethrough_offset,
+            decoration_metrics.strikethrough_thickness,
+        ),
+    ];
+
+    for (start, width) in lines.iter().copied() {
+        for (is_drawn, offset, thickness) in decorations {
+            if is_drawn && width > 0.0 {
+                renderer.render_rectangle(
+                    start + DVec2::Y * offset,
+                    width,
+                    thickness,
+                    DVec2::ZERO,
+                    0.0,
+                    color.into(),
+                );
+            }
+        }
+    }
+}
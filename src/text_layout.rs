@@ -0,0 +1,313 @@
+use anchor2d::{Anchor2D, HorizontalAnchor, VerticalAnchorContext, VerticalAnchorValue};
+use glam::{DVec2, dvec2};
+
+const ELLIPSIS: &str = "...";
+
+/// Where each line of a text box is placed along its width.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+    /// Widens the spaces of every line but the last of each paragraph until
+    /// the line fills the width.
+    Justify,
+}
+
+/// What happens to the lines that do not fit in the height of a text box.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextOverflow {
+    /// Draws every line, past the bottom of the box.
+    #[default]
+    Visible,
+    /// Cuts everything drawn to the box.
+    Clip,
+    /// Leaves out the lines that do not fit and ends the last line drawn
+    /// with an ellipsis.
+    Ellipsis,
+}
+
+/// How text is laid out inside of a box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextBox {
+    /// The width lines are wrapped to, at spaces where possible and inside of
+    /// words wider than the box otherwise.
+    pub width: f64,
+    /// The height `overflow` applies to, or `None` for a box as tall as its
+    /// lines.
+    pub height: Option<f64>,
+    pub align: TextAlign,
+    /// The distance from the top of a line to the top of the next, as a
+    /// multiple of the text size.
+    pub line_height: f64,
+    pub overflow: TextOverflow,
+}
+
+impl TextBox {
+    pub fn new(
+        width: f64,
+        height: Option<f64>,
+        align: TextAlign,
+        line_height: f64,
+        overflow: TextOverflow,
+    ) -> Self {
+        Self {
+            width,
+            height,
+            align,
+            line_height,
+            overflow,
+        }
+    }
+
+    /// A box that wraps left aligned lines to `width` and grows to fit them.
+    pub fn wrapped(width: f64) -> Self {
+        Self::new(width, None, TextAlign::Left, 1.0, TextOverflow::Visible)
+    }
+
+    /// Lays out `text` at `size` with the box anchored at `position`, with
+    /// `measure` returning the width of a line of text at `size`.
+    pub(crate) fn layout(
+        &self,
+        text: &str,
+        position: DVec2,
+        anchor: Anchor2D,
+        size: f64,
+        measure: impl Fn(&str) -> f64,
+    ) -> TextBoxLayout {
+        let line_step = size * self.line_height;
+        let mut lines = wrap_text(text, self.width, &measure);
+
+        if let (TextOverflow::Ellipsis, Some(height)) = (self.overflow, self.height) {
+            let line_count = ((height - size + line_step) / line_step).floor().max(0.0) as usize;
+
+            if lines.len() > line_count {
+                lines.truncate(line_count);
+
+                if let Some(line) = lines.last_mut() {
+                    *line = TextLine {
+                        words: vec![ellipsize(&line.get_text(), self.width, &measure)],
+                        is_last: true,
+                    };
+                }
+            }
+        }
+
+        let height = self
+            .height
+            .unwrap_or(line_step * lines.len().saturating_sub(1) as f64 + size);
+        let origin = get_box_origin(position, anchor, self.width, height);
+
+        let mut runs = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            let y = origin.y + line_step * i as f64;
+
+            if self.align == TextAlign::Justify && !line.is_last && line.words.len() > 1 {
+                let widths = line
+                    .words
+                    .iter()
+                    .map(|word| measure(word))
+                    .collect::<Vec<f64>>();
+                let gap = (self.width - widths.iter().sum::<f64>()) / (line.words.len() - 1) as f64;
+
+                let mut x = origin.x;
+
+                for (word, width) in line.words.iter().zip(widths) {
+                    runs.push((word.clone(), dvec2(x, y)));
+
+                    x += width + gap;
+                }
+            } else {
+                let text = line.get_text();
+                let width = measure(&text);
+
+                let x = match self.align {
+                    TextAlign::Left | TextAlign::Justify => origin.x,
+                    TextAlign::Center => origin.x + (self.width - width) / 2.0,
+                    TextAlign::Right => origin.x + self.width - width,
+                };
+
+                runs.push((text, dvec2(x, y)));
+            }
+        }
+
+        TextBoxLayout {
+            origin,
+            height,
+            runs,
+        }
+    }
+}
+
+/// A text box laid out in the coordinates it is drawn in.
+pub(crate) struct TextBoxLayout {
+    /// The top left corner of the box.
+    pub origin: DVec2,
+    pub height: f64,
+    /// Each piece of text to draw with the given top left corner.
+    pub runs: Vec<(String, DVec2)>,
+}
+
+/// A wrapped line of text.
+struct TextLine {
+    words: Vec<String>,
+    /// Whether the line ends a paragraph, which keeps it from being justified.
+    is_last: bool,
+}
+
+impl TextLine {
+    fn get_text(&self) -> String {
+        self.words.join(" ")
+    }
+}
+
+fn get_box_origin(position: DVec2, anchor: Anchor2D, width: f64, height: f64) -> DVec2 {
+    let x = match anchor.get_horizontal() {
+        HorizontalAnchor::Left => position.x,
+        HorizontalAnchor::Center => position.x - width / 2.0,
+        HorizontalAnchor::Right => position.x - width,
+    };
+
+    let vertical_anchor = anchor.get_vertical();
+
+    let y = match (vertical_anchor.get_context(), vertical_anchor.get_value()) {
+        (VerticalAnchorContext::Graphics, VerticalAnchorValue::Bottom) => position.y - height,
+        (VerticalAnchorContext::Math, VerticalAnchorValue::Bottom) => position.y,
+        (_, VerticalAnchorValue::Center) => position.y - height / 2.0,
+        (VerticalAnchorContext::Graphics, VerticalAnchorValue::Top) => position.y,
+        (VerticalAnchorContext::Math, VerticalAnchorValue::Top) => position.y - height,
+    };
+
+    dvec2(x, y)
+}
+
+/// Breaks every paragraph of `text` into lines no wider than `width`.
+fn wrap_text(text: &str, width: f64, measure: &impl Fn(&str) -> f64) -> Vec<TextLine> {
+    let mut lines = Vec::new();
+
+    for paragraph in text.split('\n') {
+        let mut words = Vec::<String>::new();
+        let mut line_width = 0.0;
+
+        for word in paragraph.split_whitespace() {
+            for piece in break_word(word, width, measure) {
+                let last_char = words.last().and_then(|word| word.chars().last());
+
+                let widened_width = match last_char {
+                    Some(last_char) => {
+                        line_width + get_added_width(last_char, &format!(" {piece}"), measure)
+                    }
+                    None => measure(&piece),
+                };
+
+                if last_char.is_some() && widened_width > width {
+                    lines.push(TextLine {
+                        words: std::mem::take(&mut words),
+                        is_last: false,
+                    });
+
+                    line_width = measure(&piece);
+                } else {
+                    line_width = widened_width;
+                }
+
+                words.push(piece);
+            }
+        }
+
+        lines.push(TextLine {
+            words,
+            is_last: true,
+        });
+    }
+
+    lines
+}
+
+/// Splits `word` into pieces no wider than `width`, keeping at least one
+/// character in each.
+fn break_word(word: &str, width: f64, measure: &impl Fn(&str) -> f64) -> Vec<String> {
+    if measure(word) <= width {
+        return vec![word.to_string()];
+    }
+
+    let mut pieces = Vec::new();
+    let mut piece = String::new();
+    let mut piece_width = 0.0;
+
+    for c in word.chars() {
+        let c_text = c.to_string();
+
+        let widened_width = match piece.chars().last() {
+            Some(last_char) => piece_width + get_added_width(last_char, &c_text, measure),
+            None => measure(&c_text),
+        };
+
+        if !piece.is_empty() && widened_width > width {
+            pieces.push(std::mem::take(&mut piece));
+            piece_width = measure(&c_text);
+        } else {
+            piece_width = widened_width;
+        }
+
+        piece.push(c);
+    }
+
+    pieces.push(piece);
+
+    pieces
+}
+
+/// Returns how much wider text ending in `last_char` gets with `text` added
+/// after it, counting the spacing and kerning between them without measuring
+/// the whole text again.
+fn get_added_width(last_char: char, text: &str, measure: &impl Fn(&str) -> f64) -> f64 {
+    measure(&format!("{last_char}{text}")) - measure(&last_char.to_string())
+}
+
+/// Shortens `text` until it fits in `width` with an ellipsis after it.
+fn ellipsize(text: &str, width: f64, measure: &impl Fn(&str) -> f64) -> String {
+    let mut text = text.to_string();
+
+    while !text.is_empty() && measure(&format!("{text}{ELLIPSIS}")) > width {
+        text.pop();
+        text.truncate(text.trim_end().len());
+    }
+
+    format!("{text}{ELLIPSIS}")
+}
+
+#[cfg(test)]
+mod tests {
+    use anchor2d::LGT;
+
+    use super::*;
+
+    #[test]
+    fn ellipsis_keeps_every_line_that_fits() {
+        let measure = |text: &str| text.chars().count() as f64 * 5.0;
+        let text = "one two three four five six seven eight nine ten eleven twelve";
+
+        for (line_height, line_count) in [(1.5, 3), (0.5, 7)] {
+            let layout = TextBox::new(
+                40.0,
+                Some(40.0),
+                TextAlign::Left,
+                line_height,
+                TextOverflow::Ellipsis,
+            )
+            .layout(text, DVec2::ZERO, LGT, 10.0, measure);
+
+            assert_eq!(layout.runs.len(), line_count);
+            assert!(
+                layout
+                    .runs
+                    .iter()
+                    .all(|(_, position)| position.y + 10.0 <= 40.0)
+            );
+            assert!(layout.runs.last().unwrap().0.ends_with(ELLIPSIS));
+        }
+    }
+}
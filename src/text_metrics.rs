@@ -0,0 +1,39 @@
+/// The size of a block of text, in the coordinates it is drawn in.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextMetrics {
+    /// The width of the widest line.
+    pub width: f64,
+    /// The distance from the top of the first line to the bottom of the last.
+    pub height: f64,
+    /// How far the font reaches above the baseline.
+    pub ascent: f64,
+    /// How far the font reaches below the baseline.
+    pub descent: f64,
+    /// How far capital letters reach above the baseline.
+    pub cap_height: f64,
+    /// The width of each line, from the first to the last.
+    pub line_widths: Vec<f64>,
+}
+
+impl TextMetrics {
+    pub fn new(
+        ascent: f64,
+        descent: f64,
+        cap_height: f64,
+        line_height: f64,
+        line_widths: Vec<f64>,
+    ) -> Self {
+        Self {
+            width: line_widths.iter().copied().fold(0.0, f64::max),
+            height: line_height * line_widths.len().saturating_sub(1) as f64 + ascent + descent,
+            ascent,
+            descent,
+            cap_height,
+            line_widths,
+        }
+    }
+
+    pub fn get_line_count(&self) -> usize {
+        self.line_widths.len()
+    }
+}
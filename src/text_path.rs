@@ -0,0 +1,137 @@
+use glam::DVec2;
+
+use crate::{Path, TextAlign};
+
+/// How text is laid out along a path, with the baseline of each character on
+/// the path and the character turned to follow it. Characters that fall
+/// before the start or past the end of the path are left out.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextPath {
+    /// How far along the path the text starts, before it is aligned.
+    pub start_offset: f64,
+    /// Where the text is placed along the rest of the path after
+    /// `start_offset`. [`TextAlign::Justify`] spreads the characters out until
+    /// the text fills it.
+    pub align: TextAlign,
+}
+
+impl TextPath {
+    pub fn new(start_offset: f64, align: TextAlign) -> Self {
+        Self {
+            start_offset,
+            align,
+        }
+    }
+
+    /// Lays out each character of `text` along `path`, flattened to within
+    /// `tolerance`, with `measure` returning the width of a piece of text.
+    /// Returns each character with where the middle of its baseline goes and
+    /// the angle it is turned by.
+    pub(crate) fn layout(
+        &self,
+        text: &str,
+        path: &Path,
+        tolerance: f64,
+        measure: impl Fn(&str) -> f64,
+    ) -> Vec<(String, DVec2, f64)> {
+        let segments = path
+            .flatten(tolerance)
+            .into_iter()
+            .flat_map(|subpath| {
+                let closing_segment = subpath
+                    .closed
+                    .then(|| (*subpath.points.last().unwrap(), subpath.points[0]));
+
+                subpath
+                    .points
+                    .windows(2)
+                    .map(|pair| (pair[0], pair[1]))
+                    .chain(closing_segment)
+                    .collect::<Vec<(DVec2, DVec2)>>()
+            })
+            .filter(|(start, end)| start != end)
+            .collect::<Vec<(DVec2, DVec2)>>();
+
+        let path_length = segments
+            .iter()
+            .map(|(start, end)| start.distance(*end))
+            .sum::<f64>();
+
+        let chars = text.chars().map(String::from).collect::<Vec<String>>();
+        let widths = chars.iter().map(|c| measure(c)).collect::<Vec<f64>>();
+
+        // Each character advances by how much wider it makes the pair with
+        // the character after it, which keeps the kerning and spacing between
+        // them without measuring the text before it again.
+        let mut before = 0.0;
+
+        let characters = chars
+            .iter()
+            .enumerate()
+            .map(|(i, c)| {
+                let character_before = before;
+
+                if let Some(next) = chars.get(i + 1) {
+                    before += measure(&format!("{c}{next}")) - widths[i + 1];
+                }
+
+                (c.clone(), character_before, widths[i])
+            })
+            .collect::<Vec<(String, f64, f64)>>();
+
+        let text_width = characters
+            .last()
+            .map(|(_, before, width)| before + width)
+            .unwrap_or(0.0);
+        let available_length = path_length - self.start_offset;
+
+        let (start, gap) = match self.align {
+            TextAlign::Left => (self.start_offset, 0.0),
+            TextAlign::Center => (
+                self.start_offset + (available_length - text_width) / 2.0,
+                0.0,
+            ),
+            TextAlign::Right => (path_length - text_width, 0.0),
+            TextAlign::Justify if characters.len() > 1 => (
+                self.start_offset,
+                (available_length - text_width) / (characters.len() - 1) as f64,
+            ),
+            TextAlign::Justify => (self.start_offset, 0.0),
+        };
+
+        characters
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, (character, before, width))| {
+                let distance = start + before + gap * i as f64 + width / 2.0;
+                let (point, direction) = get_point_along(&segments, distance)?;
+
+                Some((character, point, direction.to_angle()))
+            })
+            .collect::<Vec<(String, DVec2, f64)>>()
+    }
+}
+
+/// Returns the point `distance` along `segments` and the direction the
+/// segments go in there, or `None` when the distance is off the segments.
+fn get_point_along(segments: &[(DVec2, DVec2)], distance: f64) -> Option<(DVec2, DVec2)> {
+    if distance < 0.0 {
+        return None;
+    }
+
+    let mut remaining = distance;
+
+    for (start, end) in segments.iter().copied() {
+        let length = start.distance(end);
+
+        if remaining <= length {
+            let direction = (end - start) / length;
+
+            return Some((start + direction * remaining, direction));
+        }
+
+        remaining -= length;
+    }
+
+    None
+}
@@ -0,0 +1,129 @@
+use glam::{DAffine2, DMat2, DVec2};
+use palette::Srgba;
+
+/// What the vertical part of a text anchor places.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TextAnchoring {
+    /// Places the top of the ascent, the middle of the capital letters or the
+    /// bottom of the descent of the font.
+    #[default]
+    Font,
+    /// Places the baseline, whatever the vertical anchor.
+    Baseline,
+}
+
+/// A rectangle drawn behind text, reaching from the top of the ascent of the
+/// first line to the bottom of the descent of the last around its widest line.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextBackground {
+    pub color: Srgba,
+    /// How far the rectangle reaches past the text on every side.
+    pub padding: f64,
+}
+
+impl TextBackground {
+    pub fn new(color: Srgba, padding: f64) -> Self {
+        Self { color, padding }
+    }
+}
+
+/// How text is drawn, besides its size and color.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TextStyle {
+    /// The name of the registered font to draw with. Text is drawn with the
+    /// font of the renderer when this is `None` or no font is registered by
+    /// this name.
+    pub font_name: Option<String>,
+    /// The names of the registered fonts that characters missing from the
+    /// font are drawn with, in the order they are tried.
+    pub fallback_font_names: Vec<String>,
+    pub anchoring: TextAnchoring,
+    /// Draws a line under each line of text, placed as the font suggests.
+    pub underline: bool,
+    /// Draws a line through each line of text, placed as the font suggests.
+    pub strikethrough: bool,
+    /// The space added between each character and the next.
+    pub letter_spacing: f64,
+    pub background: Option<TextBackground>,
+    /// The linear transform text is drawn with around its anchor point, like
+    /// a rotation, a non-uniform scale or a skew for faux italics. Text is
+    /// measured without it.
+    pub transform: DMat2,
+}
+
+impl TextStyle {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        font_name: Option<String>,
+        fallback_font_names: Vec<String>,
+        anchoring: TextAnchoring,
+        underline: bool,
+        strikethrough: bool,
+        letter_spacing: f64,
+        background: Option<TextBackground>,
+        transform: DMat2,
+    ) -> Self {
+        Self {
+            font_name,
+            fallback_font_names,
+            anchoring,
+            underline,
+            strikethrough,
+            letter_spacing,
+            background,
+            transform,
+        }
+    }
+
+    /// Draws with the registered font named `font_name`.
+    pub fn font(font_name: String) -> Self {
+        Self {
+            font_name: Some(font_name),
+            ..Self::default()
+        }
+    }
+
+    /// Draws with the font of the renderer, anchored at the baseline.
+    pub fn baseline() -> Self {
+        Self {
+            anchoring: TextAnchoring::Baseline,
+            ..Self::default()
+        }
+    }
+
+    /// Draws with the font of the renderer, turned by `rotation` around the
+    /// anchor point.
+    pub fn rotated(rotation: f64) -> Self {
+        Self {
+            transform: DMat2::from_angle(rotation),
+            ..Self::default()
+        }
+    }
+
+    /// Draws with the font of the renderer, slanted by `angle` so the tops of
+    /// the characters lean to the right for positive angles.
+    pub fn skewed(angle: f64) -> Self {
+        Self {
+            transform: DMat2::from_cols(DVec2::X, DVec2::new(-angle.tan(), 1.0)),
+            ..Self::default()
+        }
+    }
+
+    /// Returns the transform that applies `transform` around `position`, or
+    /// `None` when there is nothing to apply.
+    pub(crate) fn get_anchored_transform(&self, position: DVec2) -> Option<DAffine2> {
+        (self.transform != DMat2::IDENTITY).then(|| {
+            DAffine2::from_translation(position)
+                * DAffine2::from_mat2(self.transform)
+                * DAffine2::from_translation(-position)
+        })
+    }
+
+    /// Returns the style without its transform.
+    pub(crate) fn untransformed(&self) -> Self {
+        Self {
+            transform: DMat2::IDENTITY,
+            ..self.clone()
+        }
+    }
+}